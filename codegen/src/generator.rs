@@ -3,6 +3,7 @@ use crate::Generate;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::Path;
 use std::process::{Command, Stdio};
 use tera::{to_value, Context, Value};
 
@@ -102,6 +103,33 @@ pub fn untuple(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
     }
 }
 
+pub fn timing(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
+    let tuple = match &value {
+        Value::Number(_) => false,
+        Value::Array(_) => true,
+        _ => false,
+    };
+
+    if tuple {
+        let v = try_get_value!("timing", "value", Vec<usize>, value);
+        Ok(to_value(format!("({}, {})", v[0], v[v.len() - 1])).unwrap())
+    } else {
+        let v = try_get_value!("timing", "value", usize, value);
+        Ok(to_value(format!("({}, {})", v, v)).unwrap())
+    }
+}
+
+pub fn effect(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
+    let v = try_get_value!("effect", "value", String, value);
+    let e = match v.as_str() {
+        "-" => "FlagEffect::Unaffected",
+        "0" => "FlagEffect::Reset",
+        "1" => "FlagEffect::Set",
+        _ => "FlagEffect::Affected",
+    };
+    Ok(to_value(e).unwrap())
+}
+
 pub fn is_cond(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
     let b = match &value {
         Value::Number(_) => false,
@@ -112,9 +140,17 @@ pub fn is_cond(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
 }
 
 pub fn run(opt: &Generate) {
+    let formatted = render(&opt.oplist, &opt.template);
+
+    let mut file = File::create(&opt.output).expect("No output");
+    file.write_all(formatted.as_bytes()).unwrap();
+}
+
+/// Render the instruction list with the templates and format it with rustfmt.
+fn render(oplist: &Path, template: &Path) -> String {
     let mut tera = compile_templates!(&format!(
         "{}/**/*",
-        opt.template.to_str().unwrap_or("templates")
+        template.to_str().unwrap_or("templates")
     ));
     tera.register_filter("hex", hex);
     tera.register_filter("getter", getter);
@@ -122,10 +158,12 @@ pub fn run(opt: &Generate) {
     tera.register_filter("untuple", untuple);
     tera.register_filter("setflag", setflag);
     tera.register_filter("is_cond", is_cond);
+    tera.register_filter("timing", timing);
+    tera.register_filter("effect", effect);
 
     let mut context = Context::new();
 
-    let file = File::open(&opt.oplist).expect("Op list not found");
+    let file = File::open(oplist).expect("Op list not found");
     let insts: Vec<Instruction> = serde_yaml::from_reader(file).expect("Unpack error");
 
     context.insert("insts", &insts);
@@ -161,6 +199,25 @@ pub fn run(opt: &Generate) {
         .read_to_string(&mut formatted)
         .expect("Couldn't read rustfmt");
 
-    let mut file = File::create(&opt.output).expect("No output");
-    file.write_all(formatted.as_bytes()).unwrap();
+    formatted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_generated_inst() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let generated = render(&root.join("inst.patched.yml"), &root.join("templates"));
+        let committed = fs::read_to_string(root.join("../core/src/inst.rs")).unwrap();
+
+        // Fails when `core/src/inst.rs` is edited by hand instead of regenerating it.
+        assert!(
+            generated == committed,
+            "core/src/inst.rs differs from the codegen output; regenerate it instead of editing by hand"
+        );
+    }
 }
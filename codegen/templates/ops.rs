@@ -110,8 +110,8 @@
 {% endmacro %}
 
 {% macro push(i) %}
-  self.push({{ i.operands[0] | getter(bits=i.bits) }});
   self.step(4);
+  self.push({{ i.operands[0] | getter(bits=i.bits) }});
 {% endmacro %}
 
 {% macro pop(i) %}
@@ -323,24 +323,27 @@
 
 {% macro call(i) %}
   let pc = {{ i.operands[0] | getter(bits=i.bits) }};
+  self.step(4);
   self.push(self.get_pc());
-  self.jump(pc);
+  self.set_pc(pc);
 {% endmacro %}
 
 {% macro callif(i) %}
   let flg = {{ i.operands[0] | getter(bits=i.bits) }};
   let pc = {{ i.operands[1] | getter(bits=i.bits) }};
   if flg {
+    self.step(4);
     self.push(self.get_pc());
-    self.jump(pc);
+    self.set_pc(pc);
     return {{ i.time[0] }}
   }
 {% endmacro %}
 
 {% macro rst(i) %}
   let pc = {{ i.operands[0] }}u16;
+  self.step(4);
   self.push(self.get_pc());
-  self.jump(pc);
+  self.set_pc(pc);
 {% endmacro %}
 
 {% macro ret(i) %}
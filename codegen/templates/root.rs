@@ -2,6 +2,7 @@
 
 use crate::cpu::{Cpu, Sys};
 use crate::alu;
+use crate::disasm::{Attrs, FlagEffect};
 use hashbrown::HashMap;
 use lazy_static::lazy_static;
 use log::*;
@@ -206,22 +207,38 @@ pub fn mnem(code: u16) -> &'static str {
     MNEMONICS.get(&code).unwrap_or(&"(unknown opcode)")
 }
 
+/// Return the static attributes of the instruction for the given opcode.
+pub fn attrs(code: u16) -> Option<Attrs> {
+    let attrs = match code {
+        {%- for i in insts %}
+        0x{{i.code | hex}} => Attrs {
+            operator: "{{i.operator}}",
+            operands: &[{% for o in i.operands %}"{{o}}",{% endfor %}],
+            size: {{i.size}},
+            time: {{i.time | timing}},
+            z: {{i.z | effect}},
+            n: {{i.n | effect}},
+            h: {{i.h | effect}},
+            c: {{i.c | effect}},
+        },
+        {%- endfor %}
+        _ => return None,
+    };
+
+    Some(attrs)
+}
+
 /// Decodes the opecode and actually executes one instruction.
 impl<T: Sys> Cpu<T> {
     /// Execute the instruction returning the expected consumed cycles
     pub fn decode(&mut self, code: u16) -> usize {
         trace!("{:04x}: {:04x}: {}", self.get_pc(), code, mnem(code));
 
-        let time = match code {
+        match code {
             {%- for i in insts -%}
             0x{{i.code | hex}} => self.op_{{i.code | hex}}(),
             {%- endfor -%}
-            _ => panic!("Invalid opcode: {:04x}: {:04x}", self.get_pc(), code),
-        };
-
-        // Every instruction consumes at least 4 cycles.
-        self.step(4);
-
-        time
+            _ => self.lock_up(code),
+        }
     }
 }
@@ -1,29 +1,25 @@
-use rgy::cpu::Cpu;
-use rgy::device::IoHandler;
-use rgy::inst::mnem;
-use rgy::mmu::{MemRead, MemWrite, Mmu};
+use rgy::debug::{
+    Access, AccessKind, Break, Control, Exception, Frame, FrameKind, Frontend, History, Location,
+    Resume, Symbols, Watchpoint,
+};
 
-use std::collections::{HashSet, VecDeque};
 use std::fmt;
-use std::string::ToString;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
 
-use signal_hook;
-
 use rustyline::error::ReadlineError;
-use rustyline::Editor;
+use rustyline::DefaultEditor;
 
 use structopt::StructOpt;
 
 use lazy_static::lazy_static;
 
-const HISTORY_FILE: &'static str = ".gy.txt";
+const HISTORY_FILE: &str = ".gy.txt";
 
 #[derive(Debug)]
-struct CmdError(String);
+pub struct CmdError(String);
 
 impl fmt::Display for CmdError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -49,91 +45,120 @@ impl From<std::num::ParseIntError> for CmdError {
     }
 }
 
-type CmdResult<T> = std::result::Result<T, CmdError>;
+pub type CmdResult<T> = std::result::Result<T, CmdError>;
 
 pub struct Debugger {
-    breaks: HashSet<u16>,
-    rd_watches: HashSet<u16>,
-    wr_watches: HashSet<u16>,
-    prompt: bool,
-    stepping: bool,
-    cpu_state: Cpu,
+    editor: DefaultEditor,
     signal: Signal,
-    exec_path: VecDeque<u16>,
+    syms: Symbols,
 }
 
 impl Debugger {
-    pub fn new() -> Self {
+    pub fn new(syms: Symbols) -> Self {
+        let mut editor = DefaultEditor::new().expect("Couldn't create line editor");
+
+        if editor.load_history(HISTORY_FILE).is_err() {
+            println!("No previous history");
+        }
+
         Self {
-            breaks: HashSet::new(),
-            rd_watches: HashSet::new(),
-            wr_watches: HashSet::new(),
-            prompt: false,
-            stepping: false,
-            cpu_state: Cpu::new(),
+            editor,
             signal: Signal::new(),
-            exec_path: VecDeque::new(),
+            syms,
         }
     }
 
-    fn add_exec_path(&mut self, pc: u16) {
-        if let Some(p) = self.exec_path.back() {
-            if pc == *p {
-                return;
-            }
+    /// Resolve the address in hex or the label into the bank (for labels) and the address.
+    fn resolve(&self, s: &str) -> CmdResult<(Option<u16>, u16)> {
+        if let Some((bank, addr)) = self.syms.find(s) {
+            return Ok((Some(bank), addr));
         }
 
-        self.exec_path.push_back(pc);
+        match parse_addr(s) {
+            Ok(addr) => Ok((None, addr)),
+            Err(_) => Err(CmdError::new(format!("Unknown address or label: {}", s))),
+        }
     }
 
-    fn check_break(&self, pc: u16, _mmu: &Mmu) -> bool {
-        if self.prompt {
-            false
-        } else if self.stepping {
-            true
-        } else {
-            self.breaks.contains(&pc)
+    /// Format the address with the nearest label if any.
+    fn describe(&self, ctl: &Control, addr: u16) -> String {
+        match self.syms.symbolize(ctl.rom_bank(), addr) {
+            Some(_) => format!("{:04x} <{}>", addr, self.syms.format(ctl.rom_bank(), addr)),
+            None => format!("{:04x}", addr),
         }
     }
 
-    fn do_break(&mut self, msg: &str, mmu: &Mmu) {
-        let (code, _) = self.cpu_state.fetch(mmu);
+    /// Format the location with the nearest label if any.
+    fn locate(&self, loc: &Location) -> String {
+        match self.syms.symbolize(loc.bank, loc.addr) {
+            Some(_) => format!("{} <{}>", loc, self.syms.format(loc.bank, loc.addr)),
+            None => loc.to_string(),
+        }
+    }
 
-        println!(
-            "{} at {:04x}: {:04x}: {}",
-            msg,
-            self.cpu_state.get_pc(),
-            code,
-            mnem(code)
-        );
+    /// Print the call stack from the innermost frame.
+    fn print_calls(&self, calls: &[Frame]) {
+        for (i, frame) in calls.iter().rev().enumerate() {
+            let kind = match frame.kind {
+                FrameKind::Call => "call",
+                FrameKind::Rst => "rst",
+                FrameKind::Interrupt => "interrupt",
+            };
 
-        self.prompt(mmu)
+            println!(
+                "#{} {} from {} ({})",
+                i,
+                self.locate(&frame.to),
+                self.locate(&frame.from),
+                kind
+            );
+        }
     }
 
-    fn prompt(&mut self, mmu: &Mmu) {
-        self.prompt = true;
-
-        let mut rl = Editor::<()>::new();
+    /// Print the last executed instructions from the oldest.
+    fn print_history<'a, I>(&self, history: I, count: usize)
+    where
+        I: DoubleEndedIterator<Item = &'a Location>,
+    {
+        let locs: Vec<_> = history.rev().take(count).collect();
 
-        if rl.load_history(HISTORY_FILE).is_err() {
-            println!("No previous history");
+        for loc in locs.into_iter().rev() {
+            println!("{}", self.locate(loc));
         }
+    }
 
-        let abort = loop {
-            let readline = rl.readline(">> ");
+    /// Format the instruction at the address, returning the line and the address of the next one.
+    fn disasm_line(&self, ctl: &Control, addr: u16) -> (String, u16) {
+        match ctl.disasm(addr) {
+            Some(inst) => {
+                let bytes: Vec<_> = (0..inst.len as u16)
+                    .map(|i| format!("{:02x}", ctl.read(addr.wrapping_add(i))))
+                    .collect();
+                let line = format!(
+                    "{:04x}: {:<8} {}",
+                    addr,
+                    bytes.join(" "),
+                    inst.symbolic(&self.syms, ctl.rom_bank())
+                );
+                (line, inst.next())
+            }
+            None => {
+                let b = ctl.read(addr);
+                let line = format!("{:04x}: {:<8} db 0x{:02x}", addr, format!("{:02x}", b), b);
+                (line, addr.wrapping_add(1))
+            }
+        }
+    }
 
-            match readline {
+    fn prompt(&mut self, ctl: &mut Control) -> Resume {
+        let resume = loop {
+            match self.editor.readline(">> ") {
                 Ok(line) => {
-                    rl.add_history_entry(line.as_str());
-
-                    match exec_cmd(self, mmu, &line) {
-                        Ok(end) => {
-                            if end {
-                                break false;
-                            } else {
-                                continue;
-                            }
-                        }
+                    let _ = self.editor.add_history_entry(line.as_str());
+
+                    match exec_cmd(self, ctl, &line) {
+                        Ok(Some(resume)) => break resume,
+                        Ok(None) => continue,
                         Err(e) => {
                             println!("{}", e);
                             continue;
@@ -142,112 +167,136 @@ impl Debugger {
                 }
                 Err(ReadlineError::Interrupted) => {
                     println!("Abort");
-                    break true;
+                    let _ = self.editor.save_history(HISTORY_FILE);
+                    std::process::exit(1);
                 }
                 Err(ReadlineError::Eof) => {
                     println!("Resume");
-                    break false;
+                    break Resume::Continue;
                 }
                 Err(err) => {
                     println!("Error: {:?}", err);
-                    break true;
+                    let _ = self.editor.save_history(HISTORY_FILE);
+                    std::process::exit(1);
                 }
             }
         };
 
-        let _ = rl.save_history(HISTORY_FILE);
-
-        if abort {
-            std::process::exit(1);
-        }
+        let _ = self.editor.save_history(HISTORY_FILE);
 
-        self.prompt = false;
+        resume
     }
 }
 
-impl rgy::debug::Debugger for Debugger {
-    fn init(&mut self, mmu: &Mmu) {
-        println!("Entering debug shell...");
-
-        self.prompt(mmu)
-    }
-
-    fn take_cpu_snapshot(&mut self, cpu: Cpu) {
-        self.cpu_state = cpu;
-    }
-
-    fn on_decode(&mut self, mmu: &Mmu) {
-        let pc = self.cpu_state.get_pc();
+impl Frontend for Debugger {
+    fn on_break(&mut self, reason: Break, ctl: &mut Control) -> Resume {
+        let msg = match reason {
+            Break::Init => "Entering debug shell".into(),
+            Break::Breakpoint => "Break".into(),
+            Break::Watchpoint(id, access) => match access.kind {
+                AccessKind::Read => format!(
+                    "Watch {}: Reading {:02x} from {:04x} by {:04x}",
+                    id, access.value, access.addr, access.pc
+                ),
+                AccessKind::Write => format!(
+                    "Watch {}: Writing {:02x} to {:04x} by {:04x}",
+                    id, access.value, access.addr, access.pc
+                ),
+                AccessKind::Execute => format!("Watch {}: Executing", id),
+            },
+            Break::Step => "Step".into(),
+            Break::Finish => "Finish".into(),
+            Break::Interrupt => "Signaled".into(),
+            Break::Exception(Exception::UnmappedIo(access)) => match access.kind {
+                AccessKind::Write => format!(
+                    "Exception: Writing {:02x} to unmapped {:04x} by {:04x}",
+                    access.value, access.addr, access.pc
+                ),
+                _ => format!(
+                    "Exception: Reading from unmapped {:04x} by {:04x}",
+                    access.addr, access.pc
+                ),
+            },
+            Break::Exception(Exception::Lockup(lockup)) => format!(
+                "Exception: CPU locked up by illegal opcode {:02x} at {:04x}",
+                lockup.opcode, lockup.pc
+            ),
+        };
 
-        self.add_exec_path(pc);
+        let pc = ctl.cpu().get_pc();
 
-        if self.check_break(pc, mmu) {
-            self.do_break("Break", mmu);
+        if let Some(label) = self.syms.label(ctl.rom_bank(), pc) {
+            println!("{}:", label);
         }
+        println!("{} at {}", msg, self.disasm_line(ctl, pc).0);
+
+        self.prompt(ctl)
     }
 
-    fn check_signal(&mut self) {
-        if self.signal.signaled() {
-            println!("Signaled.");
-            self.stepping = true;
-        }
+    fn interrupted(&mut self) -> bool {
+        self.signal.signaled()
     }
-}
 
-impl IoHandler for Debugger {
-    fn on_read(&mut self, mmu: &Mmu, addr: u16) -> MemRead {
-        if self.rd_watches.contains(&addr) {
-            self.do_break(&format!("Reading {:04x}", addr), mmu);
-        }
+    fn on_io(&mut self, access: Access, name: &'static str) {
+        let dir = match access.kind {
+            AccessKind::Write => "<-",
+            _ => "->",
+        };
 
-        MemRead::PassThrough
+        println!(
+            "{:04x}: {:<5} {} {:02x}",
+            access.pc, name, dir, access.value
+        );
     }
 
-    fn on_write(&mut self, mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
-        if self.wr_watches.contains(&addr) {
-            self.do_break(&format!("Writing {:02x} to {:04x}", value, addr), mmu);
+    fn on_exit(&mut self, calls: &[Frame], history: &History) {
+        if !std::thread::panicking() {
+            return;
         }
 
-        MemWrite::PassThrough
+        println!("Call stack at the crash:");
+        self.print_calls(calls);
+        println!("Last executed instructions:");
+        self.print_history(history.iter(), 32);
     }
 }
 
-fn exec_cmd(inner: &mut Debugger, mmu: &Mmu, line: &str) -> CmdResult<bool> {
+fn exec_cmd(inner: &mut Debugger, ctl: &mut Control, line: &str) -> CmdResult<Option<Resume>> {
     let cmd = match line.split_whitespace().next() {
         Some(cmd) => cmd,
-        None => return Ok(false),
+        None => return Ok(None),
     };
 
     match find_cmd(cmd) {
-        Some(cmd) => (cmd.handler)(inner, mmu, line),
+        Some(cmd) => (cmd.handler)(inner, ctl, line),
         None => Err(CmdError::new(format!("Command not found: {}", line))),
     }
 }
 
 fn find_cmd(s: &str) -> Option<&'static CmdInfo> {
-    for cmd in COMMANDS.iter() {
-        if cmd.name == s || cmd.short == Some(s) {
-            return Some(cmd);
-        }
-    }
-
-    None
+    COMMANDS
+        .iter()
+        .find(|cmd| cmd.name == s || cmd.short == Some(s))
 }
 
+type Handler =
+    dyn Fn(&mut Debugger, &mut Control, &str) -> CmdResult<Option<Resume>> + Send + Sync + 'static;
+
 struct CmdInfo {
     name: &'static str,
     short: Option<&'static str>,
     desc: &'static str,
-    handler: Box<dyn Fn(&mut Debugger, &Mmu, &str) -> CmdResult<bool> + Send + Sync + 'static>,
+    handler: Box<Handler>,
 }
 
+/// Command which returns `Some` to resume the execution.
 trait CmdHandler: StructOpt + Sized {
-    fn handle(&self, inner: &mut Debugger, mmu: &Mmu) -> CmdResult<bool>;
+    fn handle(&self, inner: &mut Debugger, ctl: &mut Control) -> CmdResult<Option<Resume>>;
 
-    fn parse(inner: &mut Debugger, mmu: &Mmu, s: &str) -> CmdResult<bool> {
+    fn parse(inner: &mut Debugger, ctl: &mut Control, s: &str) -> CmdResult<Option<Resume>> {
         let s = s.split_whitespace();
         match Self::from_iter_safe(s) {
-            Ok(p) => p.handle(inner, mmu),
+            Ok(p) => p.handle(inner, ctl),
             Err(e) => Err(CmdError::new(e)),
         }
     }
@@ -259,7 +308,9 @@ macro_rules! cc {
             name: $name,
             desc: $desc,
             short: $short,
-            handler: Box::new(|inner, mmu, line| $handler::parse(inner, mmu, line)),
+            handler: Box::new(|inner: &mut Debugger, ctl: &mut Control, line: &str| {
+                $handler::parse(inner, ctl, line)
+            }),
         });
     };
 }
@@ -268,7 +319,14 @@ lazy_static! {
     static ref COMMANDS: Vec<CmdInfo> = {
         let mut m = Vec::new();
         cc!(m, "break", Some("b"), "Manage break points.", CmdBreak);
-        cc!(m, "watch", Some("w"), "Manage memory watches.", CmdWatch);
+        cc!(m, "watch", Some("w"), "Manage watch points.", CmdWatch);
+        cc!(
+            m,
+            "trace",
+            Some("t"),
+            "Trace I/O register access.",
+            CmdTrace
+        );
         cc!(
             m,
             "help",
@@ -278,14 +336,28 @@ lazy_static! {
         );
         cc!(m, "quit", None, "Quit this emulator.", CmdQuit);
         cc!(m, "cont", Some("c"), "Continue execution.", CmdContinue);
-        cc!(m, "step", Some("n"), "Step execution.", CmdStep);
+        cc!(m, "step", Some("s"), "Step execution.", CmdStep);
+        cc!(m, "next", Some("n"), "Step execution over calls.", CmdNext);
+        cc!(
+            m,
+            "finish",
+            Some("f"),
+            "Run until the current function returns.",
+            CmdFinish
+        );
         cc!(m, "dump", Some("d"), "Dump information.", CmdDump);
+        cc!(m, "set", None, "Modify registers or memory.", CmdSet);
+        cc!(m, "sym", None, "Manage symbols.", CmdSym);
         m
     };
 }
 
 fn parse_addr(s: &str) -> CmdResult<u16> {
-    u16::from_str_radix(s, 16).map_err(|e| CmdError::new(e))
+    u16::from_str_radix(s, 16).map_err(CmdError::new)
+}
+
+fn parse_byte(s: &str) -> CmdResult<u8> {
+    u8::from_str_radix(s, 16).map_err(CmdError::new)
 }
 
 #[derive(StructOpt, Debug)]
@@ -294,16 +366,16 @@ enum CmdBreak {
     /// Add a break point
     #[structopt(name = "add")]
     Add {
-        /// Address in hex
-        #[structopt(name = "addr", parse(try_from_str = "parse_addr"))]
-        addr: u16,
+        /// Address in hex or label
+        #[structopt(name = "addr")]
+        addr: String,
     },
     /// Remove a break point
     #[structopt(name = "remove")]
     Remove {
-        /// Address in hex
-        #[structopt(name = "addr", parse(try_from_str = "parse_addr"))]
-        addr: u16,
+        /// Address in hex or label
+        #[structopt(name = "addr")]
+        addr: String,
     },
     /// List break points
     #[structopt(name = "list")]
@@ -311,32 +383,168 @@ enum CmdBreak {
 }
 
 impl CmdHandler for CmdBreak {
-    fn handle(&self, inner: &mut Debugger, _mmu: &Mmu) -> CmdResult<bool> {
+    fn handle(&self, inner: &mut Debugger, ctl: &mut Control) -> CmdResult<Option<Resume>> {
         match self {
             CmdBreak::Add { addr } => {
-                if inner.breaks.insert(*addr) {
-                    println!("Set break point at {:04x}", addr);
+                let set = match inner.resolve(addr)? {
+                    (Some(bank), addr) => ctl.set_banked_breakpoint(bank, addr),
+                    (None, addr) => ctl.set_breakpoint(addr),
+                };
+
+                if set {
+                    println!("Set break point at {}", addr);
                 } else {
-                    println!("Break point already set at {:04x}", addr);
+                    println!("Break point already set at {}", addr);
                 }
             }
             CmdBreak::Remove { addr } => {
-                if inner.breaks.remove(&addr) {
-                    println!("Remove break point at {:04x}", addr);
+                let (_, a) = inner.resolve(addr)?;
+
+                if ctl.remove_breakpoint(a) {
+                    println!("Remove break point at {}", addr);
                 } else {
-                    println!("Break point isn't set at {:04x}", addr);
+                    println!("Break point isn't set at {}", addr);
                 }
             }
             CmdBreak::List => {
                 println!("Break points: ");
 
-                for addr in inner.breaks.iter() {
-                    println!("* {:04x}", addr);
+                for (addr, bank) in ctl.breakpoints() {
+                    match bank {
+                        Some(bank) => println!(
+                            "* {:02x}:{:04x} <{}>",
+                            bank,
+                            addr,
+                            inner.syms.format(bank, addr)
+                        ),
+                        None => println!("* {}", inner.describe(ctl, addr)),
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "watch", about = "Manage watch points.")]
+enum CmdWatch {
+    /// Add a watch point
+    #[structopt(name = "add")]
+    Add {
+        /// Start address in hex
+        #[structopt(name = "start", parse(try_from_str = parse_addr))]
+        start: u16,
+        /// End address in hex (inclusive)
+        #[structopt(name = "end", parse(try_from_str = parse_addr))]
+        end: Option<u16>,
+        /// Watch read access
+        #[structopt(long = "read", short = "r")]
+        read: bool,
+        /// Watch write access
+        #[structopt(long = "write", short = "w")]
+        write: bool,
+        /// Watch execution
+        #[structopt(long = "exec", short = "x")]
+        exec: bool,
+        /// Watch only the access with the value in hex
+        #[structopt(long = "value", short = "v", parse(try_from_str = parse_byte))]
+        value: Option<u8>,
+    },
+    /// Remove a watch point
+    #[structopt(name = "remove")]
+    Remove {
+        /// Watch point id
+        #[structopt(name = "id")]
+        id: usize,
+    },
+    /// List watch points
+    #[structopt(name = "list")]
+    List,
+}
+
+impl CmdHandler for CmdWatch {
+    fn handle(&self, _inner: &mut Debugger, ctl: &mut Control) -> CmdResult<Option<Resume>> {
+        match self {
+            CmdWatch::Add {
+                start,
+                end,
+                read,
+                write,
+                exec,
+                value,
+            } => {
+                let end = end.unwrap_or(*start);
+
+                if end < *start {
+                    return Err(CmdError::new("End address is lower than start address"));
+                }
+
+                // Watch both read and write if no access kind is specified.
+                let any = !read && !write && !exec;
+
+                let id = ctl.add_watchpoint(
+                    Watchpoint::new(*start, end)
+                        .read(*read || any)
+                        .write(*write || any)
+                        .execute(*exec)
+                        .value(*value),
+                );
+
+                println!("Set watch point {} at {:04x}-{:04x}", id, start, end);
+            }
+            CmdWatch::Remove { id } => {
+                if ctl.remove_watchpoint(*id) {
+                    println!("Remove watch point {}", id);
+                } else {
+                    println!("Watch point {} isn't set", id);
+                }
+            }
+            CmdWatch::List => {
+                println!("Watch points: ");
+
+                for (id, w) in ctl.watchpoints() {
+                    println!(
+                        "{}: {:04x}-{:04x} ({}{}{}){}",
+                        id,
+                        w.start,
+                        w.end,
+                        if w.read { 'r' } else { '_' },
+                        if w.write { 'w' } else { '_' },
+                        if w.execute { 'x' } else { '_' },
+                        w.value
+                            .map(|v| format!(" value {:02x}", v))
+                            .unwrap_or_default()
+                    );
                 }
             }
         }
 
-        Ok(false)
+        Ok(None)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "trace", about = "Trace I/O register access.")]
+enum CmdTrace {
+    /// Start tracing
+    #[structopt(name = "on")]
+    On,
+    /// Stop tracing
+    #[structopt(name = "off")]
+    Off,
+}
+
+impl CmdHandler for CmdTrace {
+    fn handle(&self, _inner: &mut Debugger, ctl: &mut Control) -> CmdResult<Option<Resume>> {
+        let on = matches!(self, CmdTrace::On);
+
+        ctl.set_io_trace(on);
+
+        println!("I/O trace {}", if on { "on" } else { "off" });
+
+        Ok(None)
     }
 }
 
@@ -345,7 +553,7 @@ impl CmdHandler for CmdBreak {
 struct CmdHelp {}
 
 impl CmdHandler for CmdHelp {
-    fn handle(&self, _inner: &mut Debugger, _mmu: &Mmu) -> CmdResult<bool> {
+    fn handle(&self, _inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
         println!("List of available commands:");
 
         for cmd in COMMANDS.iter() {
@@ -361,7 +569,7 @@ impl CmdHandler for CmdHelp {
             );
         }
 
-        Ok(false)
+        Ok(None)
     }
 }
 
@@ -370,9 +578,11 @@ impl CmdHandler for CmdHelp {
 struct CmdQuit {}
 
 impl CmdHandler for CmdQuit {
-    fn handle(&self, _inner: &mut Debugger, _mmu: &Mmu) -> CmdResult<bool> {
+    fn handle(&self, inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
         println!("Quit.");
 
+        let _ = inner.editor.save_history(HISTORY_FILE);
+
         std::process::exit(1)
     }
 }
@@ -382,12 +592,10 @@ impl CmdHandler for CmdQuit {
 struct CmdContinue {}
 
 impl CmdHandler for CmdContinue {
-    fn handle(&self, inner: &mut Debugger, _mmu: &Mmu) -> CmdResult<bool> {
+    fn handle(&self, _inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
         println!("Continue.");
 
-        inner.stepping = false;
-
-        Ok(true)
+        Ok(Some(Resume::Continue))
     }
 }
 
@@ -396,12 +604,28 @@ impl CmdHandler for CmdContinue {
 struct CmdStep {}
 
 impl CmdHandler for CmdStep {
-    fn handle(&self, inner: &mut Debugger, _mmu: &Mmu) -> CmdResult<bool> {
-        println!("Step.");
+    fn handle(&self, _inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
+        Ok(Some(Resume::Step))
+    }
+}
 
-        inner.stepping = true;
+#[derive(StructOpt, Debug)]
+#[structopt(name = "next", about = "Step execution over calls.")]
+struct CmdNext {}
 
-        Ok(true)
+impl CmdHandler for CmdNext {
+    fn handle(&self, _inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
+        Ok(Some(Resume::Next))
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "finish", about = "Run until the current function returns.")]
+struct CmdFinish {}
+
+impl CmdHandler for CmdFinish {
+    fn handle(&self, _inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
+        Ok(Some(Resume::Finish))
     }
 }
 
@@ -422,36 +646,54 @@ enum CmdDump {
     #[structopt(name = "mem")]
     Mem {
         /// The start of the memory region to dump
-        #[structopt(name = "from", parse(try_from_str = "parse_addr"))]
+        #[structopt(name = "from", parse(try_from_str = parse_addr))]
         from: u16,
         /// The end of the memory region to dump (inclusive)
-        #[structopt(name = "to", parse(try_from_str = "parse_addr"))]
+        #[structopt(name = "to", parse(try_from_str = parse_addr))]
         to: u16,
     },
-    /// Execution path
-    #[structopt(name = "path")]
-    Path {
-        /// The number of step to dump
-        #[structopt(name = "size", default_value = "10")]
-        size: usize,
+    /// Dump call stack
+    #[structopt(name = "calls")]
+    Calls,
+    /// Dump recently executed instructions
+    #[structopt(name = "history")]
+    History {
+        /// The number of instructions to dump
+        #[structopt(name = "count", default_value = "20")]
+        count: usize,
+    },
+    /// Disassemble instructions
+    #[structopt(name = "code")]
+    Code {
+        /// The address in hex or label to start disassembling (defaults to pc)
+        #[structopt(name = "addr")]
+        addr: Option<String>,
+        /// The number of instructions to disassemble
+        #[structopt(name = "count", default_value = "10")]
+        count: usize,
     },
 }
 
 impl CmdHandler for CmdDump {
-    fn handle(&self, inner: &mut Debugger, mmu: &Mmu) -> CmdResult<bool> {
+    fn handle(&self, inner: &mut Debugger, ctl: &mut Control) -> CmdResult<Option<Resume>> {
         match self {
             CmdDump::Cpu => {
-                println!("{}", inner.cpu_state);
+                println!("{}", ctl.cpu());
             }
             CmdDump::Stack { size } => {
-                let sp = inner.cpu_state.get_sp();
+                let sp = ctl.cpu().get_sp();
 
                 for i in 0..*size {
                     let (p, of) = sp.overflowing_add(i * 2);
                     if of {
                         break;
                     }
-                    println!("{}: {:04x} [{:04x}]", i + 1, p, mmu.get16(p));
+                    println!(
+                        "{}: {:04x} [{}]",
+                        i + 1,
+                        p,
+                        inner.describe(ctl, ctl.read16(p))
+                    );
                 }
             }
             CmdDump::Mem { from, to } => {
@@ -483,7 +725,7 @@ impl CmdHandler for CmdDump {
                         print!("{:04x}: ", i);
                     }
 
-                    let b = mmu.get8(i);
+                    let b = ctl.read(i);
 
                     if i % 2 == 0 {
                         print!("{:02x}", b);
@@ -500,127 +742,99 @@ impl CmdHandler for CmdDump {
                     println!()
                 }
             }
-            CmdDump::Path { size } => {
-                for (i, pc) in inner.exec_path.iter().rev().take(*size).enumerate() {
-                    let mut cpu = inner.cpu_state.clone();
-                    cpu.set_pc(*pc);
-                    let (code, _) = cpu.fetch(mmu);
+            CmdDump::Calls => inner.print_calls(ctl.call_stack()),
+            CmdDump::History { count } => inner.print_history(ctl.history(), *count),
+            CmdDump::Code { addr, count } => {
+                let mut addr = match addr {
+                    Some(addr) => inner.resolve(addr)?.1,
+                    None => ctl.cpu().get_pc(),
+                };
+
+                for _ in 0..*count {
+                    if let Some(label) = inner.syms.label(ctl.rom_bank(), addr) {
+                        println!("{}:", label);
+                    }
 
-                    println!("-{}: {:04x}: {}", i, pc, mnem(code));
+                    let (line, next) = inner.disasm_line(ctl, addr);
+                    println!("{}", line);
+                    addr = next;
                 }
             }
         }
 
-        Ok(false)
+        Ok(None)
     }
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "watch", about = "Manage watch points.")]
-enum CmdWatch {
-    /// Add a watch point
-    #[structopt(name = "add")]
-    Add {
-        /// Address in hex
-        #[structopt(name = "addr", parse(try_from_str = "parse_addr"))]
-        addr: u16,
-        /// Add watch only for read access
-        #[structopt(long = "readonly", short = "r")]
-        readonly: bool,
-        /// Add watch only for write access
-        #[structopt(long = "writeonly", short = "w")]
-        writeonly: bool,
+#[structopt(name = "set", about = "Modify registers or memory.")]
+enum CmdSet {
+    /// Set a register (a, f, b, c, d, e, h, l, af, bc, de, hl, sp or pc)
+    #[structopt(name = "reg")]
+    Reg {
+        /// Register name
+        #[structopt(name = "name")]
+        name: String,
+        /// Value in hex
+        #[structopt(name = "value", parse(try_from_str = parse_addr))]
+        value: u16,
     },
-    /// Remove a watch point
-    #[structopt(name = "remove")]
-    Remove {
+    /// Write bytes to memory
+    #[structopt(name = "mem")]
+    Mem {
         /// Address in hex
-        #[structopt(name = "addr", parse(try_from_str = "parse_addr"))]
+        #[structopt(name = "addr", parse(try_from_str = parse_addr))]
         addr: u16,
-        /// Remove watch only for read access
-        #[structopt(long = "readonly", short = "r")]
-        readonly: bool,
-        /// Remove watch only for write access
-        #[structopt(long = "writeonly", short = "w")]
-        writeonly: bool,
+        /// Bytes in hex
+        #[structopt(name = "values", required = true, parse(try_from_str = parse_byte))]
+        values: Vec<u8>,
     },
-    /// List watch points
-    #[structopt(name = "list")]
-    List,
 }
 
-impl CmdHandler for CmdWatch {
-    fn handle(&self, inner: &mut Debugger, _mmu: &Mmu) -> CmdResult<bool> {
+impl CmdHandler for CmdSet {
+    fn handle(&self, _inner: &mut Debugger, ctl: &mut Control) -> CmdResult<Option<Resume>> {
         match self {
-            CmdWatch::Add {
-                addr,
-                readonly,
-                writeonly,
-            } => {
-                if *readonly && *writeonly {
-                    println!("Nothing set because both readonly and writeonly are set");
-                    return Ok(false);
-                }
-                if !writeonly {
-                    if inner.rd_watches.insert(*addr) {
-                        println!("Set read watch at {:04x}", addr);
+            CmdSet::Reg { name, value } => {
+                let cpu = ctl.cpu_mut();
+                let v = *value;
+                let byte = || {
+                    if v > 0xff {
+                        Err(CmdError::new(format!("{} is 8-bit register", name)))
                     } else {
-                        println!("Read watch already set at {:04x}", addr);
-                    }
-                }
-                if !readonly {
-                    if inner.wr_watches.insert(*addr) {
-                        println!("Set write watch at {:04x}", addr);
-                    } else {
-                        println!("Write watch already set at {:04x}", addr);
+                        Ok(v as u8)
                     }
+                };
+
+                match name.as_str() {
+                    "a" => cpu.set_a(byte()?),
+                    "f" => cpu.set_af((cpu.get_af() & 0xff00) | byte()? as u16),
+                    "b" => cpu.set_b(byte()?),
+                    "c" => cpu.set_c(byte()?),
+                    "d" => cpu.set_d(byte()?),
+                    "e" => cpu.set_e(byte()?),
+                    "h" => cpu.set_h(byte()?),
+                    "l" => cpu.set_l(byte()?),
+                    "af" => cpu.set_af(v),
+                    "bc" => cpu.set_bc(v),
+                    "de" => cpu.set_de(v),
+                    "hl" => cpu.set_hl(v),
+                    "sp" => cpu.set_sp(v),
+                    "pc" => cpu.set_pc(v),
+                    _ => return Err(CmdError::new(format!("Unknown register: {}", name))),
                 }
+
+                println!("Set {} to {:x}", name, v);
             }
-            CmdWatch::Remove {
-                addr,
-                readonly,
-                writeonly,
-            } => {
-                if *readonly && *writeonly {
-                    println!("Nothing unset because both readonly and writeonly are set");
-                    return Ok(false);
-                }
-                if !writeonly {
-                    if inner.rd_watches.remove(&addr) {
-                        println!("Remove read watch at {:04x}", addr);
-                    } else {
-                        println!("Read watch is already unset at {:04x}", addr);
-                    }
-                }
-                if !readonly {
-                    if inner.wr_watches.remove(&addr) {
-                        println!("Remove writeonly watch at {:04x}", addr);
-                    } else {
-                        println!("Write watch is already unset at {:04x}", addr);
-                    }
+            CmdSet::Mem { addr, values } => {
+                for (i, v) in values.iter().enumerate() {
+                    ctl.write(addr.wrapping_add(i as u16), *v);
                 }
-            }
-            CmdWatch::List => {
-                println!("Watch points: ");
-
-                for addr in inner.rd_watches.union(&inner.wr_watches) {
-                    let wr = if inner.wr_watches.contains(addr) {
-                        'w'
-                    } else {
-                        '_'
-                    };
-                    let rd = if inner.rd_watches.contains(addr) {
-                        'r'
-                    } else {
-                        '_'
-                    };
 
-                    println!("* {:04x} ({}{})", addr, rd, wr);
-                }
+                println!("Wrote {} bytes at {:04x}", values.len(), addr);
             }
         }
 
-        Ok(false)
+        Ok(None)
     }
 }
 
@@ -631,7 +845,7 @@ struct Signal {
 impl Signal {
     fn new() -> Signal {
         let sig = Arc::new(AtomicBool::new(false));
-        signal_hook::flag::register(signal_hook::SIGINT, sig.clone())
+        signal_hook::flag::register(signal_hook::consts::SIGINT, sig.clone())
             .expect("Couldn't hook signal");
         Signal { sig }
     }
@@ -640,3 +854,45 @@ impl Signal {
         self.sig.swap(false, Ordering::Relaxed)
     }
 }
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "sym", about = "Manage symbols.")]
+enum CmdSym {
+    /// Load a symbol file generated by RGBDS, replacing the current symbols
+    #[structopt(name = "load")]
+    Load {
+        /// Path to the symbol file
+        #[structopt(name = "path")]
+        path: String,
+    },
+    /// Show the location of a label
+    #[structopt(name = "find")]
+    Find {
+        /// Label name
+        #[structopt(name = "name")]
+        name: String,
+    },
+}
+
+impl CmdHandler for CmdSym {
+    fn handle(&self, inner: &mut Debugger, _ctl: &mut Control) -> CmdResult<Option<Resume>> {
+        match self {
+            CmdSym::Load { path } => {
+                inner.syms = load_symbols(path)?;
+                println!("Loaded {} symbols", inner.syms.len());
+            }
+            CmdSym::Find { name } => match inner.syms.find(name) {
+                Some((bank, addr)) => println!("{}: {:02x}:{:04x}", name, bank, addr),
+                None => println!("Label not found: {}", name),
+            },
+        }
+
+        Ok(None)
+    }
+}
+
+/// Load a symbol file generated by RGBDS.
+pub fn load_symbols<P: AsRef<std::path::Path>>(path: P) -> CmdResult<Symbols> {
+    let s = std::fs::read_to_string(path)?;
+    Symbols::parse(&s).map_err(CmdError::new)
+}
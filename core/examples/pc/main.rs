@@ -1,14 +1,15 @@
-// mod debug;
+mod debug;
 mod hardware;
 mod loader;
 
 use crate::{
-    // debug::Debugger,
+    debug::Debugger,
     hardware::Hardware,
     loader::{load_rom, Loader},
 };
 
 use log::*;
+use rgy::debug::{Monitor, Tracer};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -29,6 +30,18 @@ pub struct Opt {
     /// Enable debug mode
     #[structopt(short = "d", long = "debug")]
     debug: bool,
+    /// Stop the debug mode on the accesses to the unmapped I/O registers
+    #[structopt(long = "strict")]
+    strict: bool,
+    /// Symbol file for the debug mode (defaults to the ROM file name with `.sym`)
+    #[structopt(short = "s", long = "sym")]
+    sym: Option<PathBuf>,
+    /// Write the execution trace in the gameboy-doctor format to the file
+    #[structopt(long = "trace", conflicts_with = "debug")]
+    trace: Option<PathBuf>,
+    /// Append the clock cycles to each trace line
+    #[structopt(long = "trace-cycles")]
+    trace_cycles: bool,
     /// RAM file name
     #[structopt(short = "r", long = "ram")]
     ram: Option<String>,
@@ -43,6 +56,7 @@ fn to_cfg(opt: Opt) -> rgy::Config {
         .freq(opt.freq)
         .rate_limit_interval(opt.interval)
         .native_speed(opt.native_speed)
+        .strict(opt.strict)
 }
 
 fn set_affinity() {
@@ -76,7 +90,7 @@ fn main() {
     let hw = Hardware::new(opt.ram.clone(), opt.color);
     let hw1 = hw.clone();
 
-    std::thread::spawn(move || {
+    let emulator = std::thread::spawn(move || {
         let (rom, hw1) = if opt.rom.is_dir() {
             let mut ldr = Loader::new(&opt.rom);
 
@@ -88,11 +102,29 @@ fn main() {
         set_affinity();
 
         if opt.debug {
-            // rgy::run_debug(to_cfg(opt), &rom, hw1, Debugger::new());
+            let syms = match &opt.sym {
+                Some(path) => debug::load_symbols(path).expect("Couldn't load symbols"),
+                None => debug::load_symbols(opt.rom.with_extension("sym")).unwrap_or_default(),
+            };
+            rgy::run_debug(to_cfg(opt), &rom, hw1, Monitor::new(Debugger::new(syms)));
+        } else if let Some(path) = &opt.trace {
+            use std::io::Write;
+
+            let file = std::fs::File::create(path).expect("Couldn't create trace file");
+            let mut file = std::io::BufWriter::new(file);
+            let tracer = Tracer::new(move |line: &str| {
+                let _ = writeln!(file, "{}", line);
+            })
+            .cycles(opt.trace_cycles);
+
+            rgy::run_debug(to_cfg(opt), &rom, hw1, tracer);
         } else {
             rgy::run(to_cfg(opt), &rom, hw1);
         }
     });
 
     hw.run();
+
+    // Wait for the emulator to stop so that it saves the RAM and flushes the trace on drop.
+    emulator.join().expect("Emulator thread panicked");
 }
@@ -0,0 +1,60 @@
+mod manifest;
+mod report;
+mod run;
+
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Opt {
+    /// Emulate Gameboy Color
+    #[structopt(short = "c", long = "color")]
+    color: bool,
+    /// Number of ROMs to run in parallel (defaults to the number of CPUs)
+    #[structopt(short = "j", long = "jobs")]
+    jobs: Option<usize>,
+    /// Manifest file listing the test ROMs and their expected results
+    #[structopt(short = "m", long = "manifest")]
+    manifest: PathBuf,
+    /// Write a JUnit-style XML report to the file
+    #[structopt(long = "junit")]
+    junit: Option<PathBuf>,
+    /// Directory containing the test ROMs
+    #[structopt(name = "DIR")]
+    dir: PathBuf,
+}
+
+fn main() {
+    let opt = Opt::from_args();
+
+    env_logger::init();
+
+    let entries = match manifest::load(&opt.manifest) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let jobs = opt.jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+
+    let outcomes = run::run_all(&entries, &opt.dir, opt.color, jobs);
+
+    report::print_matrix(&entries, &outcomes);
+
+    if let Some(path) = &opt.junit {
+        if let Err(e) = report::write_junit(path, &entries, &outcomes) {
+            eprintln!("Couldn't write {}: {}", path.display(), e);
+            std::process::exit(2);
+        }
+    }
+
+    if outcomes.iter().any(|o| o.status != run::Status::Pass) {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,140 @@
+use rgy::{cpu::CPU_FREQ_HZ, VRAM_HEIGHT, VRAM_WIDTH};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// Cycle budget used when the manifest entry doesn't specify it (2 minutes in emulated time).
+const DEFAULT_BUDGET: u64 = 120 * CPU_FREQ_HZ as u64;
+
+/// Expected result of a test ROM.
+#[derive(Debug, Clone)]
+pub enum Expect {
+    /// The ROM sends the text to the serial port.
+    Serial(String),
+    /// The ROM draws the frame on the display.
+    Display(Vec<u32>),
+    /// The ROM loads the Fibonacci numbers to registers B/C/D/E/H/L (Mooneye test suite).
+    Fibonacci,
+}
+
+/// Test ROM listed in the manifest.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Path to the ROM relative to the ROM directory.
+    pub rom: PathBuf,
+    /// Expected result.
+    pub expect: Expect,
+    /// Clock cycles to run the ROM until it gives up.
+    pub budget: u64,
+}
+
+/// Load the manifest file.
+///
+/// Each line describes a test ROM with `|`-separated fields:
+///
+/// ```text
+/// # ROM                        | expectation                   | cycle budget (optional)
+/// blargg/instr_timing.gb       | serial: instr_timing\n\n\nPassed
+/// blargg/halt_bug.gb           | display: expects/halt_bug.txt | 100000000
+/// mooneye/timer/tim00.gb       | fibonacci
+/// ```
+///
+/// The serial text accepts `\n` and `\\` escapes. The display file is relative to the manifest
+/// and has the same format as the expected display files of the test harness.
+/// Empty lines and lines starting with `#` are ignored.
+pub fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    let text =
+        read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| {
+            parse_entry(line, base).map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e))
+        })
+        .collect()
+}
+
+fn parse_entry(line: &str, base: &Path) -> Result<Entry, String> {
+    let fields: Vec<&str> = line.split('|').map(|f| f.trim()).collect();
+
+    let (rom, expect, budget) = match fields.as_slice() {
+        [rom, expect] => (rom, expect, None),
+        [rom, expect, budget] => (rom, expect, Some(budget)),
+        _ => return Err("expected 2 or 3 fields".into()),
+    };
+
+    let budget = match budget {
+        Some(b) => b
+            .replace('_', "")
+            .parse()
+            .map_err(|e| format!("invalid cycle budget {:?}: {}", b, e))?,
+        None => DEFAULT_BUDGET,
+    };
+
+    Ok(Entry {
+        rom: PathBuf::from(rom),
+        expect: parse_expect(expect, base)?,
+        budget,
+    })
+}
+
+fn parse_expect(s: &str, base: &Path) -> Result<Expect, String> {
+    let (kind, value) = match s.split_once(':') {
+        Some((kind, value)) => (kind.trim(), value.trim_start()),
+        None => (s, ""),
+    };
+
+    match kind {
+        "serial" => Ok(Expect::Serial(unescape(value))),
+        "display" => load_display(&base.join(value)).map(Expect::Display),
+        "fibonacci" => Ok(Expect::Fibonacci),
+        _ => Err(format!("unknown expectation {:?}", kind)),
+    }
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn load_display(path: &Path) -> Result<Vec<u32>, String> {
+    let pixels: Vec<u32> = read_to_string(path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?
+        .chars()
+        .filter_map(|ch| match ch {
+            '.' => Some(0xdddddd),
+            '+' => Some(0xaaaaaa),
+            '0' => Some(0x888888),
+            '#' => Some(0x555555),
+            _ => None,
+        })
+        .collect();
+
+    if pixels.len() != VRAM_WIDTH * VRAM_HEIGHT {
+        return Err(format!(
+            "{} has {} pixels, expected {}",
+            path.display(),
+            pixels.len(),
+            VRAM_WIDTH * VRAM_HEIGHT
+        ));
+    }
+
+    Ok(pixels)
+}
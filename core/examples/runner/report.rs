@@ -0,0 +1,123 @@
+use crate::manifest::Entry;
+use crate::run::{Outcome, Status};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Print the pass/fail matrix of the test ROMs.
+pub fn print_matrix(entries: &[Entry], outcomes: &[Outcome]) {
+    let width = entries
+        .iter()
+        .map(|e| e.rom.display().to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(3);
+
+    println!(
+        "{:width$}  {:6}  {:>12}  {:>8}  DETAIL",
+        "ROM",
+        "RESULT",
+        "CYCLES",
+        "TIME",
+        width = width
+    );
+
+    for (entry, outcome) in entries.iter().zip(outcomes) {
+        let (result, detail) = match &outcome.status {
+            Status::Pass => ("PASS", ""),
+            Status::Fail(reason) => ("FAIL", reason.as_str()),
+        };
+
+        println!(
+            "{:width$}  {:6}  {:>12}  {:>7.2}s  {}",
+            entry.rom.display(),
+            result,
+            outcome.cycles,
+            outcome.time.as_secs_f64(),
+            detail,
+            width = width
+        );
+    }
+
+    let passed = outcomes.iter().filter(|o| o.status == Status::Pass).count();
+
+    println!();
+    println!("{} passed, {} failed", passed, outcomes.len() - passed);
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::new();
+
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c if c.is_control() && c != '\n' && c != '\t' => {
+                out.push_str(&format!("&#x{:x};", c as u32))
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Write the JUnit-style XML report of the test ROMs.
+pub fn write_junit(path: &Path, entries: &[Entry], outcomes: &[Outcome]) -> io::Result<()> {
+    let mut f = File::create(path)?;
+
+    let failures = outcomes.iter().filter(|o| o.status != Status::Pass).count();
+    let time: f64 = outcomes.iter().map(|o| o.time.as_secs_f64()).sum();
+
+    writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        f,
+        r#"<testsuites tests="{}" failures="{}" time="{:.3}">"#,
+        outcomes.len(),
+        failures,
+        time
+    )?;
+    writeln!(
+        f,
+        r#"  <testsuite name="rgy" tests="{}" failures="{}" time="{:.3}">"#,
+        outcomes.len(),
+        failures,
+        time
+    )?;
+
+    for (entry, outcome) in entries.iter().zip(outcomes) {
+        let rom = entry.rom.display().to_string();
+        let classname = entry
+            .rom
+            .parent()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+
+        write!(
+            f,
+            r#"    <testcase name="{}" classname="{}" time="{:.3}">"#,
+            escape(&rom),
+            escape(&classname),
+            outcome.time.as_secs_f64()
+        )?;
+
+        if let Status::Fail(reason) = &outcome.status {
+            write!(
+                f,
+                r#"<failure message="{}">cycles: {}</failure>"#,
+                escape(reason),
+                outcome.cycles
+            )?;
+        }
+
+        writeln!(f, "</testcase>")?;
+    }
+
+    writeln!(f, "  </testsuite>")?;
+    writeln!(f, "</testsuites>")?;
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use crate::manifest::{Entry, Expect};
+use rgy::{
+    cpu::Cpu,
+    debug::{RegisterProbe, Signature},
+    Config, Key, Stream, VRAM_HEIGHT, VRAM_WIDTH,
+};
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Result of a test ROM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+}
+
+/// Result of a test ROM with the statistics.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub status: Status,
+    /// Emulated clock cycles consumed.
+    pub cycles: u64,
+    /// Wall-clock time consumed.
+    pub time: Duration,
+}
+
+type Verdict = Rc<RefCell<Option<Status>>>;
+
+fn judge(verdict: &Verdict, status: Status) {
+    verdict.borrow_mut().get_or_insert(status);
+}
+
+struct Hardware {
+    expect: Expect,
+    serial: String,
+    frame: Vec<u32>,
+    verdict: Verdict,
+}
+
+impl rgy::Hardware for Hardware {
+    fn vram_update(&mut self, line: usize, buffer: &[u32]) {
+        let Expect::Display(expected) = &self.expect else {
+            return;
+        };
+
+        self.frame[line * VRAM_WIDTH..(line + 1) * VRAM_WIDTH].copy_from_slice(buffer);
+
+        if line == VRAM_HEIGHT - 1 && &self.frame == expected {
+            judge(&self.verdict, Status::Pass);
+        }
+    }
+
+    fn joypad_pressed(&mut self, _: Key) -> bool {
+        false
+    }
+
+    fn sound_play(&mut self, _: Box<dyn Stream>) {}
+
+    fn clock(&mut self) -> u64 {
+        let epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        epoch.as_micros() as u64
+    }
+
+    fn send_byte(&mut self, b: u8) {
+        let Expect::Serial(expected) = &self.expect else {
+            return;
+        };
+
+        self.serial.push(b as char);
+
+        if !expected.starts_with(&self.serial) {
+            judge(
+                &self.verdict,
+                Status::Fail(format!("unexpected serial output: {:?}", self.serial)),
+            );
+        } else if expected.len() == self.serial.len() {
+            judge(&self.verdict, Status::Pass);
+        }
+    }
+
+    fn recv_byte(&mut self) -> Option<u8> {
+        None
+    }
+
+    fn sched(&mut self) -> bool {
+        self.verdict.borrow().is_none()
+    }
+
+    fn load_ram(&mut self, size: usize) -> Vec<u8> {
+        vec![0; size]
+    }
+
+    fn save_ram(&mut self, _: &[u8]) {}
+}
+
+fn run_rom(entry: &Entry, dir: &Path, color: bool) -> (Status, u64) {
+    let rom = match std::fs::read(dir.join(&entry.rom)) {
+        Ok(rom) => rom,
+        Err(e) => return (Status::Fail(format!("Couldn't read ROM: {}", e)), 0),
+    };
+
+    let verdict = Verdict::default();
+    let hw = Hardware {
+        expect: entry.expect.clone(),
+        serial: String::new(),
+        frame: vec![0; VRAM_WIDTH * VRAM_HEIGHT],
+        verdict: verdict.clone(),
+    };
+    let fibonacci = matches!(entry.expect, Expect::Fibonacci);
+    let probe_verdict = verdict.clone();
+    let probe = RegisterProbe::new(move |sig, cpu: &Cpu| {
+        if !fibonacci {
+            return;
+        }
+        match sig {
+            Signature::Pass => judge(&probe_verdict, Status::Pass),
+            Signature::Fail => judge(
+                &probe_verdict,
+                Status::Fail(format!("failure signature at pc {:04x}", cpu.get_pc())),
+            ),
+        }
+    });
+
+    let cfg = Config::new().native_speed(true).color(color);
+    let mut sys = rgy::System::new(cfg, &rom, hw, probe);
+
+    while sys.poll() {
+        if let Some(lockup) = sys.lockup() {
+            let status = Status::Fail(format!(
+                "CPU locked up by illegal opcode {:02x} at {:04x}",
+                lockup.opcode, lockup.pc
+            ));
+            return (status, sys.cycles());
+        }
+        if sys.cycles() >= entry.budget {
+            let status = Status::Fail(format!("cycle budget {} exhausted", entry.budget));
+            return (status, sys.cycles());
+        }
+    }
+
+    let status = verdict
+        .borrow_mut()
+        .take()
+        .expect("stopped without verdict");
+    (status, sys.cycles())
+}
+
+fn panic_message(e: &(dyn Any + Send)) -> &str {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+fn run_one(entry: &Entry, dir: &Path, color: bool) -> Outcome {
+    let now = Instant::now();
+
+    let (status, cycles) = match catch_unwind(AssertUnwindSafe(|| run_rom(entry, dir, color))) {
+        Ok(result) => result,
+        Err(e) => (Status::Fail(format!("panicked: {}", panic_message(&*e))), 0),
+    };
+
+    Outcome {
+        status,
+        cycles,
+        time: now.elapsed(),
+    }
+}
+
+/// Run the test ROMs in parallel with the given number of threads.
+/// The outcomes are returned in the same order as the entries.
+pub fn run_all(entries: &[Entry], dir: &Path, color: bool, jobs: usize) -> Vec<Outcome> {
+    let queue = Mutex::new(0..entries.len());
+    let outcomes = Mutex::new(vec![None; entries.len()]);
+
+    thread::scope(|s| {
+        for _ in 0..jobs.max(1) {
+            s.spawn(|| loop {
+                let Some(index) = queue.lock().unwrap().next() else {
+                    break;
+                };
+
+                let outcome = run_one(&entries[index], dir, color);
+
+                eprintln!(
+                    "{}: {}",
+                    entries[index].rom.display(),
+                    match &outcome.status {
+                        Status::Pass => "pass",
+                        Status::Fail(_) => "fail",
+                    }
+                );
+
+                outcomes.lock().unwrap()[index] = Some(outcome);
+            });
+        }
+    });
+
+    outcomes
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|outcome| outcome.expect("ROM not run"))
+        .collect()
+}
@@ -14,10 +14,14 @@ impl Cgb {
         }
     }
 
-    pub fn try_switch_speed(&mut self) {
+    /// Switch the speed if requested by KEY1. Returns `true` if switched.
+    pub fn try_switch_speed(&mut self) -> bool {
         if self.speed_switch {
             self.double_speed = !self.double_speed;
             self.speed_switch = false;
+            true
+        } else {
+            false
         }
     }
 
@@ -44,3 +48,28 @@ impl Cgb {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_speed_switch() {
+        let mut cgb = Cgb::new(true);
+
+        assert!(!cgb.try_switch_speed());
+        assert_eq!(cgb.read_speed_switch(), 0x00);
+
+        cgb.write_speed_switch(0x01);
+        assert_eq!(cgb.read_speed_switch(), 0x01);
+        assert!(cgb.try_switch_speed());
+        assert!(cgb.double_speed());
+        assert_eq!(cgb.read_speed_switch(), 0x80);
+
+        // DMG ignores the request.
+        let mut cgb = Cgb::new(false);
+        cgb.write_speed_switch(0x01);
+        assert!(!cgb.try_switch_speed());
+        assert_eq!(cgb.read_speed_switch(), 0xff);
+    }
+}
@@ -16,10 +16,6 @@ impl ClockDivider {
         }
     }
 
-    pub fn set_target_clock_rate(&mut self, target_clock_rate: usize) {
-        self.target_clock_rate = target_clock_rate;
-    }
-
     pub fn step(&mut self, cycles: usize) -> usize {
         self.counter += cycles * self.target_clock_rate;
 
@@ -30,12 +26,6 @@ impl ClockDivider {
         times
     }
 
-    pub fn step_one(&mut self, cycles: usize) -> bool {
-        let times = self.step(cycles);
-        assert!(times <= 1);
-        times == 1
-    }
-
     pub fn reset(&mut self) {
         self.counter = 0;
     }
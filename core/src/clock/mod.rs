@@ -1,5 +1,4 @@
 mod clock_divider;
-mod prescaled_timer;
 mod timer;
 
-pub use self::{clock_divider::ClockDivider, prescaled_timer::PrescaledTimer, timer::Timer};
+pub use self::{clock_divider::ClockDivider, timer::Timer};
@@ -40,14 +40,6 @@ impl Timer {
         self.interval = interval;
     }
 
-    pub fn counter(&self) -> usize {
-        self.counter
-    }
-
-    pub fn set_counter(&mut self, counter: usize) {
-        self.counter = counter;
-    }
-
     /// Get the remaining ticks until the timer expires next.
     pub fn remaining(&self) -> usize {
         self.interval.saturating_sub(self.counter)
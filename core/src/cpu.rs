@@ -1,10 +1,32 @@
+use crate::debug::{Access, AccessKind};
 use crate::mmu::Mmu;
-use alloc::fmt;
+use alloc::{fmt, vec::Vec};
 use log::*;
 
 /// CPU frequency in Hz.
 pub const CPU_FREQ_HZ: usize = 4_194_304;
 
+/// Effect of STOP instruction on the CPU, decided by the system state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stop {
+    /// STOP behaves as a 2-byte instruction, skipping the following byte.
+    pub skip: bool,
+    /// The CPU enters HALT mode instead of STOP mode.
+    pub halt: bool,
+}
+
+/// Lockup of the CPU caused by an illegal opcode.
+///
+/// The opcodes 0xd3, 0xdb, 0xdd, 0xe3, 0xe4, 0xeb, 0xec, 0xed, 0xf4, 0xfc and 0xfd stop the CPU forever,
+/// ignoring interrupts, while the other devices keep running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lockup {
+    /// Address of the illegal opcode.
+    pub pc: u16,
+    /// The illegal opcode.
+    pub opcode: u8,
+}
+
 /// Interface for CPU to interact with memory/devices
 pub trait Sys {
     /// Get the interrupt vector address clearing the interrupt flag state
@@ -36,7 +58,12 @@ pub trait Sys {
     fn step(&mut self, cycles: usize);
 
     /// Stop instruction is called.
-    fn stop(&mut self);
+    fn stop(&mut self) -> Stop;
+
+    /// Check if the CPU is stalled by the DMA transfer or STOP mode.
+    fn stalled(&self) -> bool {
+        false
+    }
 }
 
 /// Represents CPU state.
@@ -53,10 +80,19 @@ pub struct Cpu<T = Mmu> {
     sp: u16,
     ime: bool,
     ei_delay: usize,
-    di_delay: usize,
     halt: bool,
     halt_bug: bool,
     cycles: usize,
+    /// Address of the instruction being executed.
+    inst_pc: u16,
+    /// Record memory accesses for the debugger.
+    trace: bool,
+    /// Memory accesses recorded in the current instruction.
+    accesses: Vec<Access>,
+    /// Interrupt vector dispatched in the current instruction.
+    dispatched: Option<u16>,
+    /// Set once the CPU executes an illegal opcode.
+    lockup: Option<Lockup>,
     sys: T,
 }
 
@@ -106,14 +142,84 @@ impl<T: Sys> Cpu<T> {
             sp: 0,
             ime: true,
             ei_delay: 0,
-            di_delay: 0,
             halt: false,
             halt_bug: false,
             cycles: 0,
+            inst_pc: 0,
+            trace: false,
+            accesses: Vec::new(),
+            dispatched: None,
+            lockup: None,
             sys,
         }
     }
 
+    /// Get the system which the CPU is attached to.
+    pub(crate) fn sys(&self) -> &T {
+        &self.sys
+    }
+
+    /// Get the mutable reference to the system which the CPU is attached to.
+    pub(crate) fn sys_mut(&mut self) -> &mut T {
+        &mut self.sys
+    }
+
+    /// Enable or disable recording memory accesses for the debugger.
+    pub(crate) fn trace_accesses(&mut self, trace: bool) {
+        self.trace = trace;
+    }
+
+    /// Take the memory accesses recorded since the last call.
+    pub(crate) fn take_accesses(&mut self) -> Vec<Access> {
+        core::mem::take(&mut self.accesses)
+    }
+
+    /// Give back the buffer taken by [`Cpu::take_accesses`] to avoid allocation.
+    pub(crate) fn reuse_accesses(&mut self, mut buf: Vec<Access>) {
+        buf.clear();
+        self.accesses = buf;
+    }
+
+    fn record(&mut self, kind: AccessKind, addr: u16, value: u8) {
+        if self.trace {
+            self.accesses.push(Access {
+                kind,
+                addr,
+                value,
+                pc: self.inst_pc,
+            });
+        }
+    }
+
+    /// Take the interrupt vector dispatched since the last call.
+    pub(crate) fn take_interrupt(&mut self) -> Option<u16> {
+        self.dispatched.take()
+    }
+
+    /// Check if the CPU is in the halt state.
+    pub(crate) fn halted(&self) -> bool {
+        self.halt
+    }
+
+    /// Get the lockup if the CPU has executed an illegal opcode.
+    pub fn lockup(&self) -> Option<Lockup> {
+        self.lockup
+    }
+
+    /// Lock up the CPU by the illegal opcode, returning the cycles consumed by the opcode fetch.
+    pub(crate) fn lock_up(&mut self, code: u16) -> usize {
+        let lockup = Lockup {
+            pc: self.inst_pc,
+            opcode: code as u8,
+        };
+
+        warn!("CPU locked up: {:04x}: {:02x}", lockup.pc, lockup.opcode);
+
+        self.lockup = Some(lockup);
+
+        4
+    }
+
     /// Switch the CPU state to halting.
     pub fn halt(&mut self) {
         debug!("Halt");
@@ -129,18 +235,31 @@ impl<T: Sys> Cpu<T> {
     /// The function fetches an instruction code from the memory,
     /// decodes it, and updates the CPU/memory state accordingly.
     /// The return value is the number of clock cycles consumed by the instruction.
-    /// If the CPU is in the halt state, the function does nothing but returns a fixed clock cycle.
+    /// If the CPU is in the halt state, stalled by DMA or locked up, the function does nothing but returns a fixed clock cycle.
     pub fn execute(&mut self) -> usize {
+        if self.sys.stalled() || self.lockup.is_some() {
+            // The CPU is paused while the DMA transfer is running,
+            // and never resumes from the lockup even on interrupts.
+            self.step(4);
+
+            let cycles = self.cycles;
+            self.cycles = 0;
+            return cycles;
+        }
+
         if self.halt {
             self.step(4);
         } else {
+            self.inst_pc = self.pc;
             let code = self.fetch_opcode();
             let time = self.decode(code);
             assert_eq!(self.cycles, time, "cycle mismatch op={:04x}", code);
         }
 
-        self.update_ime();
-        self.check_interrupt();
+        if self.lockup.is_none() {
+            self.update_ime();
+            self.check_interrupt();
+        }
 
         // Get the cycles consumed and reset
         let cycles = self.cycles;
@@ -154,25 +273,19 @@ impl<T: Sys> Cpu<T> {
         self.sys.step(cycles);
     }
 
-    /// Handles DI
+    /// Handles DI, which disables interrupts immediately and cancels the pending EI.
     pub fn di(&mut self) {
-        self.di_delay = 2;
+        self.ei_delay = 0;
+        self.disable_interrupt();
     }
 
-    /// Handles EI
+    /// Handles EI, which enables interrupts after the next instruction.
     pub fn ei(&mut self) {
         self.ei_delay = 2;
     }
 
     /// Update IME
     fn update_ime(&mut self) {
-        if self.di_delay > 0 {
-            if self.di_delay == 1 {
-                self.ime = false;
-            }
-            self.di_delay -= 1;
-        }
-
         if self.ei_delay > 0 {
             if self.ei_delay == 1 {
                 self.ime = true;
@@ -206,34 +319,74 @@ impl<T: Sys> Cpu<T> {
             return;
         }
 
-        let value = match self.sys.pop_int_vec() {
-            Some(value) => value,
-            None => return,
-        };
-
-        debug!("Interrupted: {:02x}", value);
+        if self.sys.peek_int_vec().is_none() {
+            return;
+        }
 
-        self.interrupted(value);
+        self.interrupted();
     }
 
-    fn interrupted(&mut self, vector_addr: u8) {
+    /// Dispatch the interrupt in 5 machine cycles: 2 wait states, pushing PC and jumping to the vector.
+    fn interrupted(&mut self) {
+        self.inst_pc = self.pc;
+
         if self.halt {
+            // Exiting HALT mode takes one more machine cycle.
             self.halt = false;
             self.step(4);
         }
 
+        if self.halt_bug {
+            // EI followed by HALT: the interrupt returns to the HALT instruction.
+            self.halt_bug = false;
+            self.pc = self.pc.wrapping_sub(1);
+        }
+
         self.disable_interrupt();
 
         // wait state
         self.step(8);
 
-        self.push(self.get_pc());
-        self.jump(vector_addr as u16);
+        let pc = self.get_pc();
+
+        let sp = self.get_sp().wrapping_sub(1);
+        self.set_sp(sp);
+        self.set8(sp, (pc >> 8) as u8);
+
+        // The vector is decided after pushing the upper byte, which can overwrite IE at 0xffff.
+        // If no interrupt is left enabled, the dispatch is cancelled and jumps to 0x0000.
+        let vector = match self.sys.pop_int_vec() {
+            Some(vector) => {
+                debug!("Interrupted: {:02x}", vector);
+                vector as u16
+            }
+            None => {
+                debug!("Interrupt cancelled by IE write");
+                0x0000
+            }
+        };
+
+        let sp = sp.wrapping_sub(1);
+        self.set_sp(sp);
+        self.set8(sp, pc as u8);
+
+        self.jump(vector);
+
+        self.dispatched = Some(vector);
     }
 
     /// Stop the CPU.
     pub fn stop(&mut self) {
-        self.sys.stop();
+        let stop = self.sys.stop();
+
+        debug!("Stop: {:?}", stop);
+
+        if stop.skip {
+            self.pc = self.pc.wrapping_add(1);
+        }
+        if stop.halt {
+            self.halt = true;
+        }
     }
 
     /// Gets the value of `z` flag in the flag register.
@@ -435,32 +588,39 @@ impl<T: Sys> Cpu<T> {
     /// Read a byte from memory
     pub fn get8(&mut self, a: u16) -> u8 {
         self.step(4);
-        self.sys.get8(a)
+        let v = self.sys.get8(a);
+        self.record(AccessKind::Read, a, v);
+        v
     }
 
-    /// Read a word from memory
+    /// Read a word from memory, one byte per machine cycle
     pub fn get16(&mut self, a: u16) -> u16 {
-        self.step(8);
-        self.sys.get16(a)
+        let l = self.get8(a);
+        let h = self.get8(a.wrapping_add(1));
+        (h as u16) << 8 | l as u16
     }
 
     /// Write a byte to memory
     pub fn set8(&mut self, a: u16, v: u8) {
         self.step(4);
         self.sys.set8(a, v);
+        self.record(AccessKind::Write, a, v);
     }
 
-    /// Write a word to memory
+    /// Write a word to memory, one byte per machine cycle
     pub fn set16(&mut self, a: u16, v: u16) {
-        self.step(8);
-        self.sys.set16(a, v)
+        self.set8(a, v as u8);
+        self.set8(a.wrapping_add(1), (v >> 8) as u8);
     }
 
     /// Pushes a 16-bit value to the stack, updating the stack pointer register.
     pub fn push(&mut self, v: u16) {
-        let p = self.get_sp().wrapping_sub(2);
-        self.set_sp(self.get_sp().wrapping_sub(2));
-        self.set16(p, v)
+        // The upper byte is written first.
+        let sp = self.get_sp().wrapping_sub(1);
+        self.set8(sp, (v >> 8) as u8);
+        let sp = sp.wrapping_sub(1);
+        self.set8(sp, v as u8);
+        self.set_sp(sp);
     }
 
     /// Pops a 16-bit value from the stack, updating the stack pointer register.
@@ -472,7 +632,9 @@ impl<T: Sys> Cpu<T> {
 
     /// Fetch a byte consuming cycles
     pub fn fetch8(&mut self) -> u8 {
-        let b = self.get8(self.get_pc());
+        // Not recorded as a data read.
+        self.step(4);
+        let b = self.sys.get8(self.get_pc());
         self.inc_pc();
         b
     }
@@ -484,13 +646,6 @@ impl<T: Sys> Cpu<T> {
         (h as u16) << 8 | l as u16
     }
 
-    /// Fetch next instruction without consuming cycles
-    fn prefetch(&mut self) -> u8 {
-        let b = self.sys.get8(self.get_pc());
-        self.inc_pc();
-        b
-    }
-
     /// Add 1 to pc unless HALT bug is triggerred
     fn inc_pc(&mut self) {
         if self.halt_bug {
@@ -502,8 +657,10 @@ impl<T: Sys> Cpu<T> {
     }
 
     /// Fetches an opcode from the memory and returns it with its length.
+    /// The fetch takes the first machine cycle of the instruction, so the memory accesses in the instruction
+    /// see the devices advanced by the preceding machine cycles.
     pub fn fetch_opcode(&mut self) -> u16 {
-        match self.prefetch() {
+        match self.fetch8() {
             0xcb => 0xcb00 | self.fetch8() as u16,
             b => b as u16,
         }
@@ -514,6 +671,7 @@ impl<T: Sys> Cpu<T> {
 mod test {
     use super::*;
     use crate::mmu::Ram;
+    use alloc::vec;
 
     fn exec(cpu: &mut Cpu<Ram>) {
         let code = cpu.fetch_opcode();
@@ -558,4 +716,310 @@ mod test {
         exec(&mut cpu); // cp e
         assert!(cpu.get_zf());
     }
+
+    struct StopSys {
+        ram: Ram,
+        stop: Stop,
+    }
+
+    impl Sys for StopSys {
+        fn pop_int_vec(&self) -> Option<u8> {
+            None
+        }
+
+        fn peek_int_vec(&self) -> Option<u8> {
+            None
+        }
+
+        fn get8(&self, addr: u16) -> u8 {
+            self.ram.get8(addr)
+        }
+
+        fn set8(&mut self, addr: u16, v: u8) {
+            self.ram.set8(addr, v)
+        }
+
+        fn step(&mut self, _: usize) {}
+
+        fn stop(&mut self) -> Stop {
+            self.stop
+        }
+    }
+
+    #[test]
+    fn op_0010() {
+        // stop
+        let run = |stop| {
+            let mut ram = Ram::new();
+            ram.write(&[0x10, 0x00]);
+            let mut cpu = Cpu::new(StopSys { ram, stop });
+            let code = cpu.fetch_opcode();
+            cpu.decode(code);
+            (cpu.get_pc(), cpu.halt)
+        };
+
+        assert_eq!(run(Stop::default()), (1, false));
+        assert_eq!(
+            run(Stop {
+                skip: true,
+                halt: false
+            }),
+            (2, false)
+        );
+        assert_eq!(
+            run(Stop {
+                skip: true,
+                halt: true
+            }),
+            (2, true)
+        );
+    }
+
+    /// System with the interrupt registers IE (0xffff) and IF (0xff0f).
+    struct IrqSys {
+        ram: Ram,
+        ie: u8,
+        iflag: core::cell::Cell<u8>,
+    }
+
+    impl IrqSys {
+        fn new(code: &[u8], ie: u8, iflag: u8) -> Self {
+            let mut ram = Ram::new();
+            ram.write(code);
+            Self {
+                ram,
+                ie,
+                iflag: core::cell::Cell::new(iflag),
+            }
+        }
+    }
+
+    impl Sys for IrqSys {
+        fn pop_int_vec(&self) -> Option<u8> {
+            let vector = self.peek_int_vec()?;
+            let bit = (vector - 0x40) / 8;
+            self.iflag.set(self.iflag.get() & !(1 << bit));
+            Some(vector)
+        }
+
+        fn peek_int_vec(&self) -> Option<u8> {
+            match self.ie & self.iflag.get() & 0x1f {
+                0 => None,
+                p => Some(0x40 + p.trailing_zeros() as u8 * 8),
+            }
+        }
+
+        fn get8(&self, addr: u16) -> u8 {
+            match addr {
+                0xffff => self.ie,
+                0xff0f => self.iflag.get(),
+                _ => self.ram.get8(addr),
+            }
+        }
+
+        fn set8(&mut self, addr: u16, v: u8) {
+            match addr {
+                0xffff => self.ie = v,
+                0xff0f => self.iflag.set(v),
+                _ => self.ram.set8(addr, v),
+            }
+        }
+
+        fn step(&mut self, _: usize) {}
+
+        fn stop(&mut self) -> Stop {
+            Stop::default()
+        }
+    }
+
+    #[test]
+    fn lockup() {
+        for op in [
+            0xd3, 0xdb, 0xdd, 0xe3, 0xe4, 0xeb, 0xec, 0xed, 0xf4, 0xfc, 0xfd,
+        ] {
+            let mut cpu = Cpu::new(IrqSys::new(&[0x00, op], 0x01, 0x01));
+            cpu.disable_interrupt();
+
+            // nop
+            cpu.execute();
+            assert_eq!(cpu.lockup(), None);
+
+            cpu.enable_interrupt();
+            assert_eq!(cpu.execute(), 4);
+            assert_eq!(cpu.lockup(), Some(Lockup { pc: 1, opcode: op }));
+
+            // The pending interrupt is never dispatched.
+            let pc = cpu.get_pc();
+            for _ in 0..10 {
+                assert_eq!(cpu.execute(), 4);
+            }
+            assert_eq!(cpu.get_pc(), pc);
+            assert_eq!(cpu.take_interrupt(), None);
+        }
+    }
+
+    #[test]
+    fn interrupt_dispatch() {
+        // nop
+        let mut cpu = Cpu::new(IrqSys::new(&[0x00], 0x05, 0x04));
+        cpu.set_sp(0xd000);
+
+        // nop (4) and the dispatch (20)
+        assert_eq!(cpu.execute(), 24);
+        assert_eq!(cpu.get_pc(), 0x50);
+        assert_eq!(cpu.get_sp(), 0xcffe);
+        assert_eq!(cpu.sys.get16(0xcffe), 0x0001);
+        assert_eq!(cpu.sys.iflag.get(), 0x00);
+        assert!(!cpu.ime);
+        assert_eq!(cpu.take_interrupt(), Some(0x50));
+    }
+
+    #[test]
+    fn interrupt_ie_push() {
+        // The upper byte of PC (0x00) is pushed to IE, which cancels the dispatch.
+        let mut cpu = Cpu::new(IrqSys::new(&[0x00], 0x01, 0x01));
+        cpu.set_sp(0x0000);
+
+        assert_eq!(cpu.execute(), 24);
+        assert_eq!(cpu.get_pc(), 0x0000);
+        assert_eq!(cpu.sys.ie, 0x00);
+        assert_eq!(cpu.sys.iflag.get(), 0x01);
+
+        // The upper byte of PC (0x02) enables another interrupt, which is dispatched instead.
+        let mut cpu = Cpu::new(IrqSys::new(&[0x00; 0x201], 0x01, 0x03));
+        cpu.set_pc(0x200);
+        cpu.set_sp(0x0000);
+
+        assert_eq!(cpu.execute(), 24);
+        assert_eq!(cpu.get_pc(), 0x48);
+        assert_eq!(cpu.sys.iflag.get(), 0x01);
+
+        // Pushing the lower byte to IE is too late to cancel the dispatch.
+        let mut cpu = Cpu::new(IrqSys::new(&[0x00], 0x01, 0x01));
+        cpu.set_sp(0x0001);
+
+        cpu.execute();
+        assert_eq!(cpu.get_pc(), 0x40);
+        assert_eq!(cpu.sys.ie, 0x01);
+    }
+
+    #[test]
+    fn interrupt_ei_di() {
+        // ei, nop, nop
+        let mut cpu = Cpu::new(IrqSys::new(&[0xfb, 0x00, 0x00], 0x01, 0x01));
+        cpu.disable_interrupt();
+        cpu.set_sp(0xd000);
+
+        // The interrupt is enabled after the instruction following EI.
+        cpu.execute();
+        assert_eq!(cpu.get_pc(), 0x0001);
+        cpu.execute();
+        assert_eq!(cpu.get_pc(), 0x40);
+        assert_eq!(cpu.sys.get16(0xcffe), 0x0002);
+
+        // ei, di: DI takes effect immediately and cancels EI.
+        let mut cpu = Cpu::new(IrqSys::new(&[0xfb, 0xf3, 0x00, 0x00], 0x01, 0x01));
+        cpu.disable_interrupt();
+
+        for _ in 0..4 {
+            cpu.execute();
+        }
+        assert_eq!(cpu.get_pc(), 0x0004);
+    }
+
+    #[test]
+    fn interrupt_halt() {
+        // halt, nop
+        let mut cpu = Cpu::new(IrqSys::new(&[0x76, 0x00], 0x01, 0x00));
+        cpu.set_sp(0xd000);
+
+        cpu.execute();
+        assert!(cpu.halt);
+        assert_eq!(cpu.execute(), 4);
+
+        // The wakeup takes one more machine cycle before the dispatch.
+        cpu.sys.iflag.set(0x01);
+        assert_eq!(cpu.execute(), 4 + 4 + 20);
+        assert_eq!(cpu.get_pc(), 0x40);
+        assert_eq!(cpu.sys.get16(0xcffe), 0x0001);
+
+        // ei, halt: the interrupt is dispatched without halting and returns to HALT.
+        let mut cpu = Cpu::new(IrqSys::new(&[0xfb, 0x76, 0x00], 0x01, 0x01));
+        cpu.disable_interrupt();
+        cpu.set_sp(0xd000);
+
+        cpu.execute();
+        cpu.execute();
+        assert!(!cpu.halt);
+        assert_eq!(cpu.get_pc(), 0x40);
+        assert_eq!(cpu.sys.get16(0xcffe), 0x0001);
+    }
+
+    /// System which records the cycles elapsed at each write.
+    #[derive(Default)]
+    struct TimingSys {
+        ram: Ram,
+        cycles: usize,
+        writes: Vec<(u16, usize)>,
+    }
+
+    impl Sys for TimingSys {
+        fn pop_int_vec(&self) -> Option<u8> {
+            None
+        }
+
+        fn peek_int_vec(&self) -> Option<u8> {
+            None
+        }
+
+        fn get8(&self, addr: u16) -> u8 {
+            self.ram.get8(addr)
+        }
+
+        fn set8(&mut self, addr: u16, v: u8) {
+            self.writes.push((addr, self.cycles));
+            self.ram.set8(addr, v)
+        }
+
+        fn step(&mut self, cycles: usize) {
+            self.cycles += cycles;
+        }
+
+        fn stop(&mut self) -> Stop {
+            Stop::default()
+        }
+    }
+
+    #[test]
+    fn access_timing() {
+        let run = |code: &[u8]| {
+            let mut sys = TimingSys::default();
+            sys.ram.write(code);
+            let mut cpu = Cpu::new(sys);
+            cpu.set_hl(0xc000);
+            cpu.set_sp(0xd000);
+            let time = cpu.execute();
+            (time, cpu.sys.writes)
+        };
+
+        // Each access happens at the end of its machine cycle, after the opcode fetch.
+        // ld (hl),a
+        assert_eq!(run(&[0x77]), (8, vec![(0xc000, 8)]));
+        // inc (hl)
+        assert_eq!(run(&[0x34]), (12, vec![(0xc000, 12)]));
+        // ld (a16),sp
+        assert_eq!(
+            run(&[0x08, 0x00, 0xc0]),
+            (20, vec![(0xc000, 16), (0xc001, 20)])
+        );
+        // push bc: the internal cycle precedes the upper byte.
+        assert_eq!(run(&[0xc5]), (16, vec![(0xcfff, 12), (0xcffe, 16)]));
+        // call a16
+        assert_eq!(
+            run(&[0xcd, 0x00, 0x10]),
+            (24, vec![(0xcfff, 20), (0xcffe, 24)])
+        );
+        // rst 0x38
+        assert_eq!(run(&[0xff]), (16, vec![(0xcfff, 12), (0xcffe, 16)]));
+    }
 }
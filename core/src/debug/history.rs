@@ -0,0 +1,202 @@
+use super::Debugger;
+use crate::cpu::Cpu;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Address with the ROM bank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    /// The ROM bank mapped when the address is in 0x4000 - 0x7fff, otherwise 0.
+    pub bank: u16,
+    /// Address.
+    pub addr: u16,
+}
+
+impl Location {
+    /// Get the location of the address with the ROM bank currently mapped.
+    pub fn of(cpu: &Cpu, addr: u16) -> Self {
+        let bank = if (0x4000..=0x7fff).contains(&addr) {
+            cpu.sys().rom_bank() as u16
+        } else {
+            0
+        };
+
+        Self { bank, addr }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x}:{:04x}", self.bank, self.addr)
+    }
+}
+
+/// How the function of the frame is entered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameKind {
+    /// `CALL` instruction.
+    Call,
+    /// `RST` instruction.
+    Rst,
+    /// Interrupt dispatch.
+    Interrupt,
+}
+
+/// Frame of the call stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// How the function is entered.
+    pub kind: FrameKind,
+    /// The call instruction, or the instruction to resume after the interrupt.
+    pub from: Location,
+    /// Entry point of the function.
+    pub to: Location,
+    /// Stack pointer where the return address is saved.
+    pub sp: u16,
+}
+
+/// Debugger which tracks the call stack.
+///
+/// Frames are pushed by taken `CALL`, `RST` and interrupts, and popped once the stack pointer goes above
+/// the return address, which covers `RET`, `RETI` and the code discarding the return address.
+#[derive(Default)]
+pub struct CallStack {
+    frames: Vec<Frame>,
+    pending: Option<Frame>,
+}
+
+impl CallStack {
+    /// Create an empty call stack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the frames from the outermost to the innermost.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Get the depth of the call stack.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Remove all the frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Pop the frames whose return address is above the stack pointer.
+    fn unwind(&mut self, sp: u16) {
+        while self.frames.last().is_some_and(|f| f.sp < sp) {
+            self.frames.pop();
+        }
+    }
+}
+
+/// Get the kind and the destination if the instruction at PC is a call to be taken.
+fn call(cpu: &Cpu) -> Option<(FrameKind, u16)> {
+    let pc = cpu.get_pc();
+    let target = || {
+        let l = cpu.sys().read(pc.wrapping_add(1));
+        let h = cpu.sys().read(pc.wrapping_add(2));
+        (h as u16) << 8 | l as u16
+    };
+
+    match cpu.sys().read(pc) {
+        0xcd => Some((FrameKind::Call, target())),
+        0xc4 if !cpu.get_zf() => Some((FrameKind::Call, target())),
+        0xcc if cpu.get_zf() => Some((FrameKind::Call, target())),
+        0xd4 if !cpu.get_cf() => Some((FrameKind::Call, target())),
+        0xdc if cpu.get_cf() => Some((FrameKind::Call, target())),
+        op if op & 0xc7 == 0xc7 => Some((FrameKind::Rst, (op & 0x38) as u16)),
+        _ => None,
+    }
+}
+
+impl Debugger for CallStack {
+    fn on_decode(&mut self, cpu: &mut Cpu) {
+        self.pending = call(cpu).map(|(kind, to)| Frame {
+            kind,
+            from: Location::of(cpu, cpu.get_pc()),
+            to: Location::of(cpu, to),
+            sp: cpu.get_sp().wrapping_sub(2),
+        });
+    }
+
+    fn on_execute(&mut self, cpu: &mut Cpu, _cycles: usize) {
+        self.unwind(cpu.get_sp());
+
+        if let Some(frame) = self.pending.take() {
+            self.frames.push(frame);
+        }
+    }
+
+    fn on_interrupt(&mut self, cpu: &mut Cpu, vector: u16) {
+        let sp = cpu.get_sp();
+        let l = cpu.sys().read(sp);
+        let h = cpu.sys().read(sp.wrapping_add(1));
+        let ret = (h as u16) << 8 | l as u16;
+
+        // The instruction may have returned right before the dispatch.
+        self.unwind(sp.wrapping_add(2));
+
+        self.frames.push(Frame {
+            kind: FrameKind::Interrupt,
+            from: Location::of(cpu, ret),
+            to: Location::of(cpu, vector),
+            sp,
+        });
+    }
+}
+
+/// Debugger which keeps the locations of the last executed instructions in a ring buffer.
+pub struct History {
+    locs: VecDeque<Location>,
+    size: usize,
+}
+
+impl History {
+    /// Create a history which keeps the last `size` instructions.
+    pub fn new(size: usize) -> Self {
+        Self {
+            locs: VecDeque::with_capacity(size),
+            size,
+        }
+    }
+
+    /// Get the locations from the oldest to the latest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Location> + '_ {
+        self.locs.iter()
+    }
+
+    /// Get the number of the recorded locations.
+    pub fn len(&self) -> usize {
+        self.locs.len()
+    }
+
+    /// Check if nothing is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.locs.is_empty()
+    }
+
+    /// Remove all the recorded locations.
+    pub fn clear(&mut self) {
+        self.locs.clear();
+    }
+}
+
+impl Debugger for History {
+    fn on_decode(&mut self, cpu: &mut Cpu) {
+        if self.size == 0 {
+            return;
+        }
+
+        if self.locs.len() == self.size {
+            self.locs.pop_front();
+        }
+
+        self.locs.push_back(Location::of(cpu, cpu.get_pc()));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::cpu::{Cpu, Lockup, Sys};
+
+mod history;
+mod io;
+mod monitor;
+mod probe;
+mod symbols;
+mod trace;
+
+pub use self::history::{CallStack, Frame, FrameKind, History, Location};
+pub use self::io::io_register_name;
+pub use self::monitor::{Break, Control, Frontend, Monitor, Resume, Watchpoint};
+pub use self::probe::{RegisterProbe, Signature};
+pub use self::symbols::{SymbolError, Symbols};
+pub use self::trace::{TraceSink, Tracer};
+
+/// Kind of memory access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessKind {
+    /// The CPU reads data from the memory.
+    Read,
+    /// The CPU writes data to the memory.
+    Write,
+    /// The CPU executes the instruction at the address.
+    Execute,
+}
+
+/// Memory access by the CPU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Access {
+    /// Kind of the access.
+    pub kind: AccessKind,
+    /// Accessed address.
+    pub addr: u16,
+    /// Value read or written. The opcode for [`AccessKind::Execute`].
+    pub value: u8,
+    /// Address of the instruction which made the access.
+    pub pc: u16,
+}
+
+/// Unexpected event in the execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exception {
+    /// The CPU accessed the I/O register which isn't mapped or implemented.
+    /// The read returned 0xff and the write was ignored.
+    /// Reported only in strict mode (see [`Config::strict`](crate::Config::strict)).
+    UnmappedIo(Access),
+    /// The CPU locked up by an illegal opcode.
+    Lockup(Lockup),
+}
+
+/// Debugger interface.
+///
+/// The users of this library can implement this interface to inspect the state of the emulator.
+pub trait Debugger {
+    /// The function is called on the initialization phase.
+    fn init(&mut self, _cpu: &mut Cpu) {}
+
+    /// The function is called right before the CPU fetches and executes an instruction.
+    /// The debugger can inspect and modify the CPU registers and the memory here.
+    /// It isn't called while the CPU is halted or stalled.
+    fn on_decode(&mut self, _cpu: &mut Cpu) {}
+
+    /// The function is called after every [`Cpu::execute`] with the consumed clock cycles,
+    /// including the cycles while the CPU is halted or stalled.
+    fn on_execute(&mut self, _cpu: &mut Cpu, _cycles: usize) {}
+
+    /// The function is called after [`Debugger::on_execute`] when the CPU dispatches an interrupt
+    /// and jumps to the vector address.
+    fn on_interrupt(&mut self, _cpu: &mut Cpu, _vector: u16) {}
+
+    /// Check if the debugger needs [`Debugger::on_access`] and [`Debugger::on_io`].
+    /// The function is called before every instruction, and the memory accesses are recorded only when it returns `true`.
+    fn watch_memory(&self) -> bool {
+        false
+    }
+
+    /// The function is called for each memory read and write by the CPU after the instruction completes.
+    /// Instruction fetches aren't included.
+    fn on_access(&mut self, _cpu: &mut Cpu, _access: Access) {}
+
+    /// The function is called for each I/O register access by the CPU with the register name
+    /// after [`Debugger::on_access`].
+    fn on_io(&mut self, _cpu: &mut Cpu, _access: Access, _name: &'static str) {}
+
+    /// The function is called for each exception raised by the instruction after [`Debugger::on_execute`].
+    fn on_exception(&mut self, _cpu: &mut Cpu, _exception: Exception) {}
+}
+
+impl dyn Debugger {
+    /// Create an empty debugger.
+    pub fn empty() -> NullDebugger {
+        NullDebugger
+    }
+}
+
+/// Empty debugger which does nothing.
+pub struct NullDebugger;
+
+impl Debugger for NullDebugger {}
+
+/// Execute one instruction notifying the debugger.
+pub(crate) fn execute<D: Debugger>(dbg: &mut D, cpu: &mut Cpu) -> usize {
+    let pc = cpu.get_pc();
+
+    let locked = cpu.lockup().is_some();
+
+    if !cpu.halted() && !cpu.sys().stalled() && !locked {
+        dbg.on_decode(cpu);
+    }
+
+    let trace = dbg.watch_memory();
+
+    cpu.trace_accesses(trace);
+
+    let time = cpu.execute();
+
+    if trace {
+        let accesses = cpu.take_accesses();
+
+        for access in accesses.iter().copied() {
+            dbg.on_access(cpu, access);
+
+            if let Some(name) = io_register_name(access.addr) {
+                dbg.on_io(cpu, access, name);
+            }
+        }
+
+        cpu.reuse_accesses(accesses);
+    }
+
+    dbg.on_execute(cpu, time);
+
+    for exception in cpu.sys_mut().take_exceptions() {
+        let exception = match exception {
+            Exception::UnmappedIo(access) => Exception::UnmappedIo(Access { pc, ..access }),
+            e => e,
+        };
+
+        dbg.on_exception(cpu, exception);
+    }
+
+    if !locked {
+        if let Some(lockup) = cpu.lockup() {
+            dbg.on_exception(cpu, Exception::Lockup(lockup));
+        }
+    }
+
+    if let Some(vector) = cpu.take_interrupt() {
+        dbg.on_interrupt(cpu, vector);
+    }
+
+    time
+}
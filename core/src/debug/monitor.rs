@@ -0,0 +1,852 @@
+use super::{Access, AccessKind, CallStack, Debugger, Exception, Frame, History, Location};
+use crate::cpu::Cpu;
+use crate::disasm::{self, Instruction};
+use alloc::collections::{BTreeMap, BTreeSet};
+
+/// Reason why the execution is stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Break {
+    /// The emulator is about to start.
+    Init,
+    /// The CPU reached a breakpoint.
+    Breakpoint,
+    /// The memory access matched the watchpoint with the id.
+    Watchpoint(usize, Access),
+    /// The CPU completed stepping.
+    Step,
+    /// The CPU returned from the function.
+    Finish,
+    /// The frontend requested to stop the execution.
+    Interrupt,
+    /// The instruction raised the exception in strict mode.
+    Exception(Exception),
+}
+
+/// How to resume the execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resume {
+    /// Run until the CPU reaches a breakpoint.
+    Continue,
+    /// Execute one instruction.
+    Step,
+    /// Execute one instruction stepping over `CALL` and `RST`.
+    Next,
+    /// Run until the CPU returns from the current function.
+    Finish,
+}
+
+/// User interface of the [`Monitor`], e.g. a command line shell.
+pub trait Frontend {
+    /// The function is called when the execution is stopped.
+    /// The frontend can inspect and modify the emulator state through `ctl` until it returns how to resume.
+    fn on_break(&mut self, reason: Break, ctl: &mut Control) -> Resume;
+
+    /// Check if the execution should be stopped, e.g. by Ctrl-C.
+    /// The function is called before every instruction.
+    fn interrupted(&mut self) -> bool {
+        false
+    }
+
+    /// The function is called for each I/O register access while the I/O trace is enabled.
+    fn on_io(&mut self, _access: Access, _name: &'static str) {}
+
+    /// The function is called when the [`Monitor`] is dropped, i.e. the emulator exits or panics,
+    /// for the post-mortem inspection.
+    fn on_exit(&mut self, _calls: &[Frame], _history: &History) {}
+}
+
+/// Condition to stop the execution on memory accesses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Watchpoint {
+    /// First address of the range.
+    pub start: u16,
+    /// Last address of the range (inclusive).
+    pub end: u16,
+    /// Stop on read.
+    pub read: bool,
+    /// Stop on write.
+    pub write: bool,
+    /// Stop on execution.
+    pub execute: bool,
+    /// Stop only when the value read, written or executed is equal.
+    pub value: Option<u8>,
+}
+
+impl Watchpoint {
+    /// Create a watchpoint for the address range, which doesn't watch any access kinds yet.
+    pub fn new(start: u16, end: u16) -> Self {
+        Self {
+            start,
+            end,
+            read: false,
+            write: false,
+            execute: false,
+            value: None,
+        }
+    }
+
+    /// Set the flag to stop on read.
+    pub fn read(mut self, read: bool) -> Self {
+        self.read = read;
+        self
+    }
+
+    /// Set the flag to stop on write.
+    pub fn write(mut self, write: bool) -> Self {
+        self.write = write;
+        self
+    }
+
+    /// Set the flag to stop on execution.
+    pub fn execute(mut self, execute: bool) -> Self {
+        self.execute = execute;
+        self
+    }
+
+    /// Stop only when the value is equal.
+    pub fn value(mut self, value: Option<u8>) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Check if the access matches the watchpoint.
+    pub fn matches(&self, access: &Access) -> bool {
+        let kind = match access.kind {
+            AccessKind::Read => self.read,
+            AccessKind::Write => self.write,
+            AccessKind::Execute => self.execute,
+        };
+
+        kind && (self.start..=self.end).contains(&access.addr)
+            && self.value.is_none_or(|v| v == access.value)
+    }
+}
+
+#[derive(Default)]
+struct Points {
+    breaks: BTreeSet<(u16, Option<u16>)>,
+    watches: BTreeMap<usize, Watchpoint>,
+    next_watch: usize,
+    trace_io: bool,
+}
+
+/// Access to the emulator state while the execution is stopped.
+pub struct Control<'a> {
+    cpu: &'a mut Cpu,
+    points: &'a mut Points,
+    calls: &'a CallStack,
+    history: &'a History,
+}
+
+impl<'a> Control<'a> {
+    /// Get the CPU state.
+    pub fn cpu(&self) -> &Cpu {
+        self.cpu
+    }
+
+    /// Get the CPU state to modify the registers.
+    pub fn cpu_mut(&mut self) -> &mut Cpu {
+        self.cpu
+    }
+
+    /// Read a byte from the memory.
+    /// Unlike the CPU, the read doesn't consume cycles and isn't blocked by the PPU or DMA.
+    pub fn read(&self, addr: u16) -> u8 {
+        self.cpu.sys().read(addr)
+    }
+
+    /// Read two bytes from the memory in little endian.
+    pub fn read16(&self, addr: u16) -> u16 {
+        let l = self.read(addr);
+        let h = self.read(addr.wrapping_add(1));
+        (h as u16) << 8 | l as u16
+    }
+
+    /// Write a byte to the memory.
+    /// The write to the ROM area goes to the cartridge mapper as the CPU does.
+    pub fn write(&mut self, addr: u16, v: u8) {
+        self.cpu.sys_mut().write(addr, v)
+    }
+
+    /// Read the opcode at the address, which includes the `0xcb` prefix if any.
+    pub fn opcode(&self, addr: u16) -> u16 {
+        match self.read(addr) {
+            0xcb => 0xcb00 | self.read(addr.wrapping_add(1)) as u16,
+            b => b as u16,
+        }
+    }
+
+    /// Disassemble the instruction at the address. Returns `None` if the opcode is invalid.
+    pub fn disasm(&self, addr: u16) -> Option<Instruction> {
+        disasm::decode_at(|a| self.read(a), addr)
+    }
+
+    /// Get the ROM bank currently mapped to 0x4000 - 0x7fff.
+    pub fn rom_bank(&self) -> u16 {
+        self.cpu.sys().rom_bank() as u16
+    }
+
+    /// Set a breakpoint. Returns `false` if it's already set.
+    pub fn set_breakpoint(&mut self, addr: u16) -> bool {
+        self.points.breaks.insert((addr, None))
+    }
+
+    /// Set a breakpoint which stops only while the ROM bank is mapped if the address is in 0x4000 - 0x7fff.
+    /// Returns `false` if it's already set.
+    pub fn set_banked_breakpoint(&mut self, bank: u16, addr: u16) -> bool {
+        self.points.breaks.insert((addr, Some(bank)))
+    }
+
+    /// Remove the breakpoints at the address in any bank. Returns `false` if none is set.
+    pub fn remove_breakpoint(&mut self, addr: u16) -> bool {
+        let len = self.points.breaks.len();
+        self.points.breaks.retain(|(a, _)| *a != addr);
+        self.points.breaks.len() != len
+    }
+
+    /// Get the addresses and the banks of the breakpoints in ascending order.
+    pub fn breakpoints(&self) -> impl Iterator<Item = (u16, Option<u16>)> + '_ {
+        self.points.breaks.iter().copied()
+    }
+
+    /// Add a watchpoint returning its id.
+    pub fn add_watchpoint(&mut self, watch: Watchpoint) -> usize {
+        let id = self.points.next_watch;
+        self.points.next_watch += 1;
+        self.points.watches.insert(id, watch);
+        id
+    }
+
+    /// Remove the watchpoint with the id. Returns `false` if it doesn't exist.
+    pub fn remove_watchpoint(&mut self, id: usize) -> bool {
+        self.points.watches.remove(&id).is_some()
+    }
+
+    /// Get the watchpoints with their ids.
+    pub fn watchpoints(&self) -> impl Iterator<Item = (usize, &Watchpoint)> + '_ {
+        self.points.watches.iter().map(|(id, w)| (*id, w))
+    }
+
+    /// Enable or disable the I/O trace, which delivers I/O register accesses to [`Frontend::on_io`].
+    pub fn set_io_trace(&mut self, enable: bool) {
+        self.points.trace_io = enable;
+    }
+
+    /// Check if the I/O trace is enabled.
+    pub fn io_trace(&self) -> bool {
+        self.points.trace_io
+    }
+
+    /// Get the call stack from the outermost to the innermost frame.
+    pub fn call_stack(&self) -> &[Frame] {
+        self.calls.frames()
+    }
+
+    /// Get the locations of the last executed instructions from the oldest to the latest.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Location> + '_ {
+        self.history.iter()
+    }
+}
+
+enum Mode {
+    /// Run until a breakpoint.
+    Run,
+    /// Stop at the next instruction.
+    Step,
+    /// Stop when the CPU comes back to the address with the stack pointer at the same depth or shallower.
+    Next { addr: u16, sp: u16 },
+    /// Stop when a return instruction makes the stack shallower than the stack pointer.
+    Finish { sp: u16 },
+}
+
+/// Length of the instruction if it's `CALL` or `RST`.
+fn call_len(op: u8) -> Option<u16> {
+    match op {
+        0xc4 | 0xcc | 0xcd | 0xd4 | 0xdc => Some(3),
+        op if op & 0xc7 == 0xc7 => Some(1),
+        _ => None,
+    }
+}
+
+fn is_ret(op: u8) -> bool {
+    matches!(op, 0xc0 | 0xc8 | 0xc9 | 0xd0 | 0xd8 | 0xd9)
+}
+
+/// Interactive debugger which implements breakpoints, watchpoints and stepping.
+///
+/// The execution is stopped right after the initialization, and the [`Frontend`] is called
+/// every time the execution is stopped.
+pub struct Monitor<F: Frontend> {
+    frontend: F,
+    points: Points,
+    calls: CallStack,
+    history: History,
+    mode: Mode,
+    /// The last instruction was a return instruction.
+    returned: bool,
+    /// Already stopped by a watchpoint in the current instruction.
+    watched: bool,
+}
+
+impl<F: Frontend> Monitor<F> {
+    /// Create a new debugger with the frontend.
+    /// The last 256 executed instructions are kept in the history.
+    pub fn new(frontend: F) -> Self {
+        Self {
+            frontend,
+            points: Points::default(),
+            calls: CallStack::new(),
+            history: History::new(256),
+            mode: Mode::Run,
+            returned: false,
+            watched: false,
+        }
+    }
+
+    /// Set the number of the executed instructions kept in the history.
+    pub fn history(mut self, size: usize) -> Self {
+        self.history = History::new(size);
+        self
+    }
+
+    /// Get the frontend.
+    pub fn frontend(&self) -> &F {
+        &self.frontend
+    }
+
+    /// Get the mutable reference to the frontend.
+    pub fn frontend_mut(&mut self) -> &mut F {
+        &mut self.frontend
+    }
+
+    fn check(&mut self, cpu: &Cpu) -> Option<Break> {
+        if self.frontend.interrupted() {
+            return Some(Break::Interrupt);
+        }
+
+        let pc = cpu.get_pc();
+        let sp = cpu.get_sp();
+
+        match self.mode {
+            Mode::Step => return Some(Break::Step),
+            Mode::Next { addr, sp: base } if pc == addr && sp >= base => {
+                return Some(Break::Step);
+            }
+            Mode::Finish { sp: base } if self.returned && sp > base => {
+                return Some(Break::Finish);
+            }
+            _ => {}
+        }
+
+        let banked = (0x4000..=0x7fff).contains(&pc);
+        let rom_bank = cpu.sys().rom_bank() as u16;
+        let hit = self
+            .points
+            .breaks
+            .range((pc, None)..=(pc, Some(u16::MAX)))
+            .any(|(_, bank)| !banked || bank.is_none_or(|b| b == rom_bank));
+
+        if hit {
+            return Some(Break::Breakpoint);
+        }
+
+        let access = Access {
+            kind: AccessKind::Execute,
+            addr: pc,
+            value: cpu.sys().read(pc),
+            pc,
+        };
+
+        self.watch(access)
+    }
+
+    fn watch(&self, access: Access) -> Option<Break> {
+        self.points
+            .watches
+            .iter()
+            .find(|(_, w)| w.matches(&access))
+            .map(|(id, _)| Break::Watchpoint(*id, access))
+    }
+
+    fn stop(&mut self, reason: Break, cpu: &mut Cpu) {
+        let mut ctl = Control {
+            cpu,
+            points: &mut self.points,
+            calls: &self.calls,
+            history: &self.history,
+        };
+
+        let resume = self.frontend.on_break(reason, &mut ctl);
+
+        let pc = cpu.get_pc();
+        let sp = cpu.get_sp();
+
+        self.mode = match resume {
+            Resume::Continue => Mode::Run,
+            Resume::Step => Mode::Step,
+            Resume::Next => match call_len(cpu.sys().read(pc)) {
+                Some(len) => Mode::Next {
+                    addr: pc.wrapping_add(len),
+                    sp,
+                },
+                None => Mode::Step,
+            },
+            Resume::Finish => Mode::Finish { sp },
+        };
+    }
+}
+
+impl<F: Frontend> Debugger for Monitor<F> {
+    fn init(&mut self, cpu: &mut Cpu) {
+        self.stop(Break::Init, cpu);
+    }
+
+    fn on_decode(&mut self, cpu: &mut Cpu) {
+        if let Some(reason) = self.check(cpu) {
+            self.stop(reason, cpu);
+        }
+
+        self.returned = is_ret(cpu.sys().read(cpu.get_pc()));
+        self.watched = false;
+
+        self.calls.on_decode(cpu);
+        self.history.on_decode(cpu);
+    }
+
+    fn on_execute(&mut self, cpu: &mut Cpu, cycles: usize) {
+        self.calls.on_execute(cpu, cycles);
+    }
+
+    fn on_interrupt(&mut self, cpu: &mut Cpu, vector: u16) {
+        self.calls.on_interrupt(cpu, vector);
+    }
+
+    fn watch_memory(&self) -> bool {
+        self.points.trace_io || self.points.watches.values().any(|w| w.read || w.write)
+    }
+
+    fn on_access(&mut self, cpu: &mut Cpu, access: Access) {
+        if self.watched {
+            return;
+        }
+
+        if let Some(reason) = self.watch(access) {
+            self.watched = true;
+            self.stop(reason, cpu);
+        }
+    }
+
+    fn on_io(&mut self, _cpu: &mut Cpu, access: Access, name: &'static str) {
+        if self.points.trace_io {
+            self.frontend.on_io(access, name);
+        }
+    }
+
+    fn on_exception(&mut self, cpu: &mut Cpu, exception: Exception) {
+        self.stop(Break::Exception(exception), cpu);
+    }
+}
+
+impl<F: Frontend> Drop for Monitor<F> {
+    fn drop(&mut self) {
+        self.frontend.on_exit(self.calls.frames(), &self.history);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cpu::Lockup;
+    use crate::debug::FrameKind;
+    use crate::hardware::{HardwareHandle, TestHardware};
+    use crate::mmu::Mmu;
+    use crate::Config;
+    use alloc::{boxed::Box, collections::VecDeque, string::ToString, vec, vec::Vec};
+
+    type Action = Box<dyn FnMut(&mut Control) -> Resume>;
+
+    #[derive(Default)]
+    struct Script {
+        log: Vec<(Break, u16)>,
+        io: Vec<(&'static str, Access)>,
+        actions: VecDeque<Action>,
+    }
+
+    impl Frontend for Script {
+        fn on_break(&mut self, reason: Break, ctl: &mut Control) -> Resume {
+            self.log.push((reason, ctl.cpu().get_pc()));
+
+            match self.actions.pop_front() {
+                Some(mut action) => action(ctl),
+                None => Resume::Continue,
+            }
+        }
+
+        fn on_io(&mut self, access: Access, name: &'static str) {
+            self.io.push((name, access));
+        }
+    }
+
+    // c000: call c010
+    // c003: nop
+    // c004: jr c004
+    // c010: ldh (47),a
+    // c012: ret
+    fn run(actions: Vec<Action>) -> (Script, Cpu) {
+        let code = [
+            (0xc000, &[0xcd, 0x10, 0xc0, 0x00, 0x18, 0xfe][..]),
+            (0xc010, &[0xe0, 0x47, 0xc9][..]),
+        ];
+
+        run_code(&Config::new(), &code, actions)
+    }
+
+    fn run_code(cfg: &Config, code: &[(u16, &[u8])], actions: Vec<Action>) -> (Script, Cpu) {
+        let hw = HardwareHandle::new(TestHardware::default());
+        let mmu = Mmu::new(hw, vec![0; 0x8000], cfg, None);
+        let mut cpu = Cpu::new(mmu);
+
+        for &(base, bytes) in code {
+            for (i, b) in bytes.iter().enumerate() {
+                cpu.sys_mut().write(base + i as u16, *b);
+            }
+        }
+        cpu.set_pc(0xc000);
+        cpu.set_sp(0xdff0);
+
+        let mut mon = Monitor::new(Script {
+            log: Vec::new(),
+            io: Vec::new(),
+            actions: actions.into(),
+        });
+
+        mon.init(&mut cpu);
+        for _ in 0..20 {
+            crate::debug::execute(&mut mon, &mut cpu);
+        }
+
+        (core::mem::take(&mut mon.frontend), cpu)
+    }
+
+    #[test]
+    fn test_breakpoint_and_step() {
+        let (script, _) = run(vec![
+            Box::new(|ctl| {
+                assert!(ctl.set_breakpoint(0xc010));
+                assert!(!ctl.set_breakpoint(0xc010));
+                // The bank doesn't matter outside the switchable ROM area.
+                assert!(ctl.set_banked_breakpoint(2, 0xc010));
+                assert_eq!(ctl.rom_bank(), 1);
+                assert_eq!(
+                    ctl.breakpoints().collect::<Vec<_>>(),
+                    vec![(0xc010, None), (0xc010, Some(2))]
+                );
+                Resume::Continue
+            }),
+            Box::new(|_| Resume::Step),
+            Box::new(|ctl| {
+                assert!(ctl.remove_breakpoint(0xc010));
+                assert!(!ctl.remove_breakpoint(0xc010));
+                Resume::Continue
+            }),
+        ]);
+
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (Break::Breakpoint, 0xc010),
+                (Break::Step, 0xc012),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next() {
+        let (script, _) = run(vec![
+            Box::new(|_| Resume::Step),
+            Box::new(|_| Resume::Next),
+            Box::new(|_| Resume::Next),
+        ]);
+
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (Break::Step, 0xc000),
+                (Break::Step, 0xc003),
+                (Break::Step, 0xc004),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finish() {
+        let (script, _) = run(vec![
+            Box::new(|ctl| {
+                ctl.set_breakpoint(0xc010);
+                Resume::Continue
+            }),
+            Box::new(|_| Resume::Finish),
+        ]);
+
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (Break::Breakpoint, 0xc010),
+                (Break::Finish, 0xc003),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_modify() {
+        let (_, cpu) = run(vec![Box::new(|ctl| {
+            // Replace the nop at c003 with `inc a`.
+            ctl.write(0xc003, 0x3c);
+            ctl.cpu_mut().set_a(0x41);
+            assert_eq!(ctl.opcode(0xc003), 0x3c);
+            assert_eq!(ctl.read16(0xc001), 0xc010);
+            assert_eq!(ctl.disasm(0xc000).unwrap().to_string(), "call 0xc010");
+            assert_eq!(ctl.disasm(0xc003).unwrap().to_string(), "inc a");
+            Resume::Continue
+        })]);
+
+        assert_eq!(cpu.get_a(), 0x42);
+    }
+
+    #[test]
+    fn test_watchpoint() {
+        let access = |kind, addr, value, pc| Access {
+            kind,
+            addr,
+            value,
+            pc,
+        };
+
+        let (script, _) = run(vec![
+            Box::new(|ctl| {
+                // The return address pushed by the call.
+                let w = Watchpoint::new(0xdfee, 0xdfef)
+                    .write(true)
+                    .value(Some(0xc0));
+                assert_eq!(ctl.add_watchpoint(w), 0);
+                let w = Watchpoint::new(0xdfee, 0xdfef).read(true);
+                assert_eq!(ctl.add_watchpoint(w), 1);
+                let w = Watchpoint::new(0xc004, 0xc004).execute(true);
+                assert_eq!(ctl.add_watchpoint(w), 2);
+                Resume::Continue
+            }),
+            Box::new(|_| Resume::Continue),
+            Box::new(|_| Resume::Continue),
+            Box::new(|ctl| {
+                assert!(ctl.remove_watchpoint(2));
+                assert!(!ctl.remove_watchpoint(2));
+                assert_eq!(ctl.watchpoints().count(), 2);
+                Resume::Continue
+            }),
+        ]);
+
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (
+                    Break::Watchpoint(0, access(AccessKind::Write, 0xdfef, 0xc0, 0xc000)),
+                    0xc010
+                ),
+                (
+                    Break::Watchpoint(1, access(AccessKind::Read, 0xdfee, 0x03, 0xc012)),
+                    0xc003
+                ),
+                (
+                    Break::Watchpoint(2, access(AccessKind::Execute, 0xc004, 0x18, 0xc004)),
+                    0xc004
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_call_stack() {
+        let loc = |addr| Location { bank: 0, addr };
+
+        let (script, _) = run(vec![
+            Box::new(|ctl| {
+                ctl.set_breakpoint(0xc012);
+                assert!(ctl.call_stack().is_empty());
+                Resume::Continue
+            }),
+            Box::new(move |ctl| {
+                assert_eq!(
+                    ctl.call_stack(),
+                    &[Frame {
+                        kind: FrameKind::Call,
+                        from: loc(0xc000),
+                        to: loc(0xc010),
+                        sp: 0xdfee,
+                    }]
+                );
+                // Raise the timer interrupt on the return.
+                ctl.write(0xffff, 0x04);
+                ctl.write(0xff0f, 0x04);
+                Resume::Step
+            }),
+            Box::new(move |ctl| {
+                assert_eq!(
+                    ctl.call_stack(),
+                    &[Frame {
+                        kind: FrameKind::Interrupt,
+                        from: loc(0xc003),
+                        to: loc(0x0050),
+                        sp: 0xdfee,
+                    }]
+                );
+                // Don't run into the boot ROM.
+                ctl.cpu_mut().set_pc(0xc004);
+                Resume::Continue
+            }),
+        ]);
+
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (Break::Breakpoint, 0xc012),
+                (Break::Step, 0x0050),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        run(vec![
+            Box::new(|ctl| {
+                ctl.set_breakpoint(0xc004);
+                Resume::Continue
+            }),
+            Box::new(|ctl| {
+                let pcs: Vec<_> = ctl.history().map(|l| l.addr).collect();
+                assert_eq!(pcs, vec![0xc000, 0xc010, 0xc012, 0xc003]);
+                assert_eq!(ctl.history().next_back().unwrap().to_string(), "00:c003");
+                Resume::Continue
+            }),
+        ]);
+    }
+
+    #[test]
+    fn test_io_trace() {
+        let (script, _) = run(vec![Box::new(|ctl| {
+            ctl.cpu_mut().set_a(0xe4);
+            ctl.set_io_trace(true);
+            Resume::Continue
+        })]);
+
+        assert_eq!(
+            script.io,
+            vec![(
+                "BGP",
+                Access {
+                    kind: AccessKind::Write,
+                    addr: 0xff47,
+                    value: 0xe4,
+                    pc: 0xc010,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_strict() {
+        let access = |kind, addr, value, pc| Access {
+            kind,
+            addr,
+            value,
+            pc,
+        };
+
+        // c000: ldh a,(03)
+        // c002: ldh (56),a
+        // c004: ldh a,(68)
+        // c006: jr c006
+        let code = [(
+            0xc000,
+            &[0xf0, 0x03, 0xe0, 0x56, 0xf0, 0x68, 0x18, 0xfe][..],
+        )];
+
+        let (script, cpu) = run_code(&Config::new().strict(true), &code, vec![]);
+
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (
+                    Break::Exception(Exception::UnmappedIo(access(
+                        AccessKind::Read,
+                        0xff03,
+                        0xff,
+                        0xc000
+                    ))),
+                    0xc002
+                ),
+                (
+                    Break::Exception(Exception::UnmappedIo(access(
+                        AccessKind::Write,
+                        0xff56,
+                        0xff,
+                        0xc002
+                    ))),
+                    0xc004
+                ),
+                (
+                    Break::Exception(Exception::UnmappedIo(access(
+                        AccessKind::Read,
+                        0xff68,
+                        0xff,
+                        0xc004
+                    ))),
+                    0xc006
+                ),
+            ]
+        );
+        // The CGB palette index register isn't mapped in DMG mode.
+        assert_eq!(cpu.get_a(), 0xff);
+
+        // In CGB mode, it reads with the unused bit 6 set.
+        let (script, cpu) = run_code(&Config::new().color(true).strict(true), &code, vec![]);
+
+        assert_eq!(script.log.len(), 3);
+        assert_eq!(cpu.get_a(), 0x40);
+
+        // The accesses are silently ignored without strict mode.
+        let (script, _) = run_code(&Config::new(), &code, vec![]);
+
+        assert_eq!(script.log, vec![(Break::Init, 0xc000)]);
+    }
+
+    #[test]
+    fn test_lockup() {
+        // c000: nop
+        // c001: illegal
+        let code = [(0xc000, &[0x00, 0xd3][..])];
+
+        let (script, cpu) = run_code(&Config::new(), &code, vec![]);
+
+        let lockup = Lockup {
+            pc: 0xc001,
+            opcode: 0xd3,
+        };
+
+        // The lockup is reported only once and the CPU never fetches again.
+        assert_eq!(
+            script.log,
+            vec![
+                (Break::Init, 0xc000),
+                (Break::Exception(Exception::Lockup(lockup)), 0xc002),
+            ]
+        );
+        assert_eq!(cpu.lockup(), Some(lockup));
+        assert_eq!(cpu.get_pc(), 0xc002);
+    }
+}
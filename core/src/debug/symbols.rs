@@ -0,0 +1,192 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// Error on parsing a symbol file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolError {
+    /// Line number (1-origin) where the error is found.
+    pub line: usize,
+}
+
+impl fmt::Display for SymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Malformed symbol at line {}", self.line)
+    }
+}
+
+/// Check if the address is in the switchable ROM bank area.
+fn is_banked(addr: u16) -> bool {
+    (0x4000..=0x7fff).contains(&addr)
+}
+
+/// Memory area which a label can cover with its offset.
+fn area(addr: u16) -> u8 {
+    match addr {
+        0x0000..=0x3fff => 0,
+        0x4000..=0x7fff => 1,
+        0x8000..=0x9fff => 2,
+        0xa000..=0xbfff => 3,
+        0xc000..=0xcfff => 4,
+        0xd000..=0xdfff => 5,
+        0xff80..=0xfffe => 6,
+        _ => 7,
+    }
+}
+
+/// Labels loaded from the symbol files generated by RGBDS (`bank:addr label`).
+///
+/// Only the banks of the switchable ROM area (0x4000 - 0x7fff) are distinguished.
+/// Labels in the other areas are looked up by the address.
+#[derive(Clone, Debug, Default)]
+pub struct Symbols {
+    labels: BTreeMap<(u16, u16), String>,
+    names: BTreeMap<String, (u16, u16)>,
+}
+
+impl Symbols {
+    /// Create an empty symbol table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the content of a symbol file. Comments start with `;`.
+    pub fn parse(s: &str) -> Result<Self, SymbolError> {
+        let mut syms = Self::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split(';').next().unwrap_or("").trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let err = || SymbolError { line: i + 1 };
+
+            let (loc, name) = line.split_once(char::is_whitespace).ok_or_else(err)?;
+            let (bank, addr) = loc.split_once(':').ok_or_else(err)?;
+            let bank = u16::from_str_radix(bank, 16).map_err(|_| err())?;
+            let addr = u16::from_str_radix(addr, 16).map_err(|_| err())?;
+
+            syms.insert(bank, addr, name.trim());
+        }
+
+        Ok(syms)
+    }
+
+    /// Add a label. The first label is kept if the location has several ones.
+    pub fn insert(&mut self, bank: u16, addr: u16, name: &str) {
+        let bank = if is_banked(addr) { bank } else { 0 };
+
+        self.labels
+            .entry((bank, addr))
+            .or_insert_with(|| name.to_string());
+        self.names.insert(name.to_string(), (bank, addr));
+    }
+
+    /// Get the number of the labels.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Check if there's no label.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Find the bank and the address of the label.
+    pub fn find(&self, name: &str) -> Option<(u16, u16)> {
+        self.names.get(name).copied()
+    }
+
+    /// Get the label at the address exactly.
+    /// `rom_bank` is the ROM bank currently mapped to 0x4000 - 0x7fff.
+    pub fn label(&self, rom_bank: u16, addr: u16) -> Option<&str> {
+        let bank = if is_banked(addr) { rom_bank } else { 0 };
+
+        self.labels.get(&(bank, addr)).map(|s| s.as_str())
+    }
+
+    /// Get the nearest label at or before the address in the same memory area with the offset from it.
+    /// `rom_bank` is the ROM bank currently mapped to 0x4000 - 0x7fff.
+    pub fn symbolize(&self, rom_bank: u16, addr: u16) -> Option<(&str, u16)> {
+        let bank = if is_banked(addr) { rom_bank } else { 0 };
+
+        let ((b, a), name) = self.labels.range(..=(bank, addr)).next_back()?;
+
+        if *b == bank && area(*a) == area(addr) {
+            Some((name.as_str(), addr - a))
+        } else {
+            None
+        }
+    }
+
+    /// Format the address with the nearest label, e.g. `Main+0x3`, or the address itself if there's none.
+    pub fn format(&self, rom_bank: u16, addr: u16) -> String {
+        match self.symbolize(rom_bank, addr) {
+            Some((name, 0)) => name.to_string(),
+            Some((name, off)) => alloc::format!("{}+0x{:x}", name, off),
+            None => alloc::format!("{:04x}", addr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SYM: &str = "; File generated by rgblink
+00:0150 Start
+00:0150 Start.entry
+00:0160 Start.loop
+01:4000 Bank1Func
+02:4000 Bank2Func
+02:4010 Bank2Func.end
+00:c000 wCounter
+00:ff80 hVBlankFlag ; comment
+";
+
+    #[test]
+    fn test_parse() {
+        let syms = Symbols::parse(SYM).unwrap();
+
+        assert_eq!(syms.len(), 8);
+        assert_eq!(syms.find("Start"), Some((0, 0x150)));
+        assert_eq!(syms.find("Bank2Func.end"), Some((2, 0x4010)));
+        assert_eq!(syms.find("Missing"), None);
+
+        assert_eq!(
+            Symbols::parse("00:0150 Start\nbroken\n").unwrap_err(),
+            SymbolError { line: 2 }
+        );
+        assert_eq!(
+            Symbols::parse("zz:0150 Start").unwrap_err(),
+            SymbolError { line: 1 }
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        let syms = Symbols::parse(SYM).unwrap();
+
+        // The first label is used for the address.
+        assert_eq!(syms.label(1, 0x150), Some("Start"));
+        assert_eq!(syms.label(1, 0x4000), Some("Bank1Func"));
+        assert_eq!(syms.label(2, 0x4000), Some("Bank2Func"));
+        assert_eq!(syms.label(3, 0x4000), None);
+
+        assert_eq!(syms.symbolize(1, 0x165), Some(("Start.loop", 5)));
+        assert_eq!(syms.symbolize(2, 0x4005), Some(("Bank2Func", 5)));
+        assert_eq!(syms.symbolize(2, 0x4100), Some(("Bank2Func.end", 0xf0)));
+        assert_eq!(syms.symbolize(3, 0x4005), None);
+        assert_eq!(syms.symbolize(1, 0x100), None);
+        // Labels don't extend over memory areas.
+        assert_eq!(syms.symbolize(1, 0x8000), None);
+        assert_eq!(syms.symbolize(1, 0xc001), Some(("wCounter", 1)));
+
+        assert_eq!(syms.format(1, 0x150), "Start");
+        assert_eq!(syms.format(1, 0x152), "Start+0x2");
+        assert_eq!(syms.format(1, 0x0100), "0100");
+        assert_eq!(syms.format(5, 0xff80), "hVBlankFlag");
+    }
+}
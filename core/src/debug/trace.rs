@@ -0,0 +1,217 @@
+use super::Debugger;
+use crate::cpu::Cpu;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Destination of the execution trace.
+pub trait TraceSink {
+    /// The function is called with each trace line without the trailing newline.
+    fn write_line(&mut self, line: &str);
+}
+
+impl<F: FnMut(&str)> TraceSink for F {
+    fn write_line(&mut self, line: &str) {
+        self(line)
+    }
+}
+
+/// Debugger which writes the CPU state before every instruction in the gameboy-doctor format:
+///
+/// ```text
+/// A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02
+/// ```
+///
+/// With [`Tracer::cycles`], each line is followed by ` CY:` and the clock cycles consumed before the instruction.
+pub struct Tracer<S> {
+    sink: S,
+    start: u16,
+    end: u16,
+    bank: Option<u16>,
+    stamp: bool,
+    cycles: u64,
+    line: String,
+}
+
+impl<S: TraceSink> Tracer<S> {
+    /// Create a tracer which traces all the instructions.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            start: 0,
+            end: 0xffff,
+            bank: None,
+            stamp: false,
+            cycles: 0,
+            line: String::new(),
+        }
+    }
+
+    /// Trace only the instructions at PC in the range (inclusive).
+    pub fn range(mut self, start: u16, end: u16) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Trace the instructions in 0x4000 - 0x7fff only while the ROM bank is mapped.
+    pub fn bank(mut self, bank: Option<u16>) -> Self {
+        self.bank = bank;
+        self
+    }
+
+    /// Append the clock cycles consumed so far to each line.
+    pub fn cycles(mut self, stamp: bool) -> Self {
+        self.stamp = stamp;
+        self
+    }
+
+    /// Get the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get the mutable reference to the sink, e.g. to flush it.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    fn matches(&self, cpu: &Cpu) -> bool {
+        let pc = cpu.get_pc();
+
+        if pc < self.start || pc > self.end {
+            return false;
+        }
+
+        match self.bank {
+            Some(bank) if (0x4000..=0x7fff).contains(&pc) => cpu.sys().rom_bank() as u16 == bank,
+            _ => true,
+        }
+    }
+}
+
+impl<S: TraceSink> Debugger for Tracer<S> {
+    fn on_decode(&mut self, cpu: &mut Cpu) {
+        if !self.matches(cpu) {
+            return;
+        }
+
+        let pc = cpu.get_pc();
+        let mem = |i| cpu.sys().read(pc.wrapping_add(i));
+
+        self.line.clear();
+
+        let _ = write!(
+            self.line,
+            "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} \
+             SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+            cpu.get_a(),
+            cpu.get_af() as u8,
+            cpu.get_b(),
+            cpu.get_c(),
+            cpu.get_d(),
+            cpu.get_e(),
+            cpu.get_h(),
+            cpu.get_l(),
+            cpu.get_sp(),
+            pc,
+            mem(0),
+            mem(1),
+            mem(2),
+            mem(3),
+        );
+
+        if self.stamp {
+            let _ = write!(self.line, " CY:{}", self.cycles);
+        }
+
+        self.sink.write_line(&self.line);
+    }
+
+    fn on_execute(&mut self, _cpu: &mut Cpu, cycles: usize) {
+        self.cycles += cycles as u64;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::{HardwareHandle, TestHardware};
+    use crate::mmu::Mmu;
+    use crate::Config;
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    // c000: ld a,42
+    // c002: inc b
+    // c003: jr c002
+    fn run<F: FnOnce(Tracer<Lines>) -> Tracer<Lines>>(setup: F) -> Vec<String> {
+        let hw = HardwareHandle::new(TestHardware::default());
+        let mmu = Mmu::new(hw, vec![0; 0x8000], &Config::new(), None);
+        let mut cpu = Cpu::new(mmu);
+
+        for (i, b) in [0x3e, 0x42, 0x04, 0x18, 0xfd].iter().enumerate() {
+            cpu.sys_mut().write(0xc000 + i as u16, *b);
+        }
+        cpu.set_pc(0xc000);
+        cpu.set_sp(0xdff0);
+
+        let mut tracer = setup(Tracer::new(Lines::default()));
+
+        for _ in 0..5 {
+            crate::debug::execute(&mut tracer, &mut cpu);
+        }
+
+        tracer.sink.0
+    }
+
+    #[derive(Default)]
+    struct Lines(Vec<String>);
+
+    impl TraceSink for Lines {
+        fn write_line(&mut self, line: &str) {
+            self.0.push(line.to_string());
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let lines = run(|t| t);
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            "A:00 F:00 B:00 C:00 D:00 E:00 H:00 L:00 SP:DFF0 PC:C000 PCMEM:3E,42,04,18"
+        );
+        assert_eq!(
+            lines[1],
+            "A:42 F:00 B:00 C:00 D:00 E:00 H:00 L:00 SP:DFF0 PC:C002 PCMEM:04,18,FD,00"
+        );
+        assert_eq!(
+            lines[2],
+            "A:42 F:00 B:01 C:00 D:00 E:00 H:00 L:00 SP:DFF0 PC:C003 PCMEM:18,FD,00,00"
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let lines = run(|t| t.range(0xc002, 0xc002));
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.contains("PC:C002")));
+
+        // The bank doesn't matter outside the switchable ROM area.
+        assert_eq!(run(|t| t.bank(Some(5))).len(), 5);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let lines = run(|t| t.cycles(true));
+
+        let stamps: Vec<_> = lines
+            .iter()
+            .map(|l| l.rsplit("CY:").next().unwrap())
+            .collect();
+
+        // ld a,d8 (8), inc b (4), jr (12), inc b (4)
+        assert_eq!(stamps, vec!["0", "8", "12", "24", "28"]);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::debug::Symbols;
+use crate::inst::attrs;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Effect of an instruction on a flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagEffect {
+    /// The flag is left unchanged.
+    Unaffected,
+    /// The flag is always cleared.
+    Reset,
+    /// The flag is always set.
+    Set,
+    /// The flag is updated according to the result.
+    Affected,
+}
+
+/// Static attributes of an instruction, generated from the instruction table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attrs {
+    /// Operator, e.g. `ld`.
+    pub operator: &'static str,
+    /// Operand templates, e.g. `["bc", "d16"]`.
+    pub operands: &'static [&'static str],
+    /// Length of the instruction in bytes including the `0xcb` prefix.
+    pub size: usize,
+    /// Clock cycles consumed when the branch is taken and not taken.
+    /// Both are the same for unconditional instructions.
+    pub time: (usize, usize),
+    /// Effect on the zero flag.
+    pub z: FlagEffect,
+    /// Effect on the subtract flag.
+    pub n: FlagEffect,
+    /// Effect on the half carry flag.
+    pub h: FlagEffect,
+    /// Effect on the carry flag.
+    pub c: FlagEffect,
+}
+
+/// Branch condition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cond {
+    /// Zero flag is cleared.
+    Nz,
+    /// Zero flag is set.
+    Z,
+    /// Carry flag is cleared.
+    Nc,
+    /// Carry flag is set.
+    C,
+}
+
+/// Decoded operand of an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    /// Register, e.g. `a` or `hl`.
+    Reg(&'static str),
+    /// Memory pointed by the register, e.g. `(hl)`.
+    Indirect(&'static str),
+    /// Memory in the I/O page pointed by the register C, i.e. `(0xff00+c)`.
+    IndirectC,
+    /// 8-bit immediate value.
+    Imm8(u8),
+    /// 16-bit immediate value.
+    Imm16(u16),
+    /// Memory at the address, from `(a16)` or `(0xff00+a8)`.
+    Addr(u16),
+    /// Signed offset added to SP.
+    Offset(i8),
+    /// Destination of a jump or a call.
+    Target(u16),
+    /// Branch condition.
+    Cond(Cond),
+    /// Bit number of `bit`, `res` and `set`.
+    Bit(u8),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Reg(r) => write!(f, "{}", r),
+            Operand::Indirect(r) => write!(f, "({})", r),
+            Operand::IndirectC => write!(f, "(0xff00+c)"),
+            Operand::Imm8(v) => write!(f, "0x{:02x}", v),
+            Operand::Imm16(v) => write!(f, "0x{:04x}", v),
+            Operand::Addr(v) => write!(f, "(0x{:04x})", v),
+            Operand::Offset(v) => write!(f, "{}", v),
+            Operand::Target(v) => write!(f, "0x{:04x}", v),
+            Operand::Cond(Cond::Nz) => write!(f, "nz"),
+            Operand::Cond(Cond::Z) => write!(f, "z"),
+            Operand::Cond(Cond::Nc) => write!(f, "nc"),
+            Operand::Cond(Cond::C) => write!(f, "c"),
+            Operand::Bit(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Decoded instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    /// Address of the instruction.
+    pub addr: u16,
+    /// Opcode, which includes the `0xcb` prefix if any.
+    pub code: u16,
+    /// Operator, e.g. `ld`.
+    pub operator: &'static str,
+    /// Operands with the actual values.
+    pub operands: Vec<Operand>,
+    /// Length of the instruction in bytes.
+    pub len: usize,
+    /// Clock cycles consumed when the branch is taken and not taken.
+    /// Both are the same for unconditional instructions.
+    pub cycles: (usize, usize),
+    /// Effects on the flags in the order of Z, N, H and C.
+    pub flags: [FlagEffect; 4],
+}
+
+impl Instruction {
+    /// Address of the next instruction in the memory.
+    pub fn next(&self) -> u16 {
+        self.addr.wrapping_add(self.len as u16)
+    }
+
+    /// Static destination of the jump, call or restart, if any.
+    /// `jp hl` and returns have no static destination.
+    pub fn target(&self) -> Option<u16> {
+        self.operands.iter().find_map(|op| match op {
+            Operand::Target(addr) => Some(*addr),
+            _ => None,
+        })
+    }
+
+    /// Check if the instruction branches only on a condition.
+    pub fn is_conditional(&self) -> bool {
+        self.operands
+            .iter()
+            .any(|op| matches!(op, Operand::Cond(_)))
+    }
+
+    /// Display the instruction showing the destinations and the memory addresses with the labels.
+    /// `rom_bank` is the ROM bank currently mapped to 0x4000 - 0x7fff.
+    pub fn symbolic<'a>(&'a self, syms: &'a Symbols, rom_bank: u16) -> Symbolic<'a> {
+        Symbolic {
+            inst: self,
+            syms: Some((syms, rom_bank)),
+        }
+    }
+}
+
+/// Instruction displayed with labels. See [`Instruction::symbolic`].
+pub struct Symbolic<'a> {
+    inst: &'a Instruction,
+    syms: Option<(&'a Symbols, u16)>,
+}
+
+impl Symbolic<'_> {
+    fn label(&self, addr: u16) -> Option<String> {
+        let (syms, rom_bank) = self.syms?;
+        syms.symbolize(rom_bank, addr)?;
+        Some(syms.format(rom_bank, addr))
+    }
+}
+
+impl fmt::Display for Symbolic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.inst.operator)?;
+
+        for (i, op) in self.inst.operands.iter().enumerate() {
+            write!(f, "{}", if i == 0 { " " } else { "," })?;
+
+            match op {
+                Operand::Target(addr) => match self.label(*addr) {
+                    Some(label) => write!(f, "{}", label)?,
+                    None => write!(f, "{}", op)?,
+                },
+                Operand::Addr(addr) => match self.label(*addr) {
+                    Some(label) => write!(f, "({})", label)?,
+                    None => write!(f, "{}", op)?,
+                },
+                op => write!(f, "{}", op)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Symbolic {
+            inst: self,
+            syms: None,
+        }
+        .fmt(f)
+    }
+}
+
+fn register(s: &'static str) -> Operand {
+    match s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(r) => Operand::Indirect(r),
+        None => Operand::Reg(s),
+    }
+}
+
+fn operand(operator: &str, template: &'static str, addr: u16, arg: &[u8]) -> Operand {
+    let d8 = || arg[0];
+    let d16 = || (arg[1] as u16) << 8 | arg[0] as u16;
+
+    match template {
+        "d8" => Operand::Imm8(d8()),
+        "d16" => Operand::Imm16(d16()),
+        "a16" if operator == "jp" || operator == "call" => Operand::Target(d16()),
+        "a16" => Operand::Imm16(d16()),
+        "(a16)" => Operand::Addr(d16()),
+        "(0xff00+a8)" => Operand::Addr(0xff00 | d8() as u16),
+        "(0xff00+c)" => Operand::IndirectC,
+        "r8" if operator == "jr" => {
+            let next = addr.wrapping_add(2);
+            Operand::Target(next.wrapping_add(d8() as i8 as u16))
+        }
+        "r8" => Operand::Offset(d8() as i8),
+        "nz" => Operand::Cond(Cond::Nz),
+        "z" => Operand::Cond(Cond::Z),
+        "nc" => Operand::Cond(Cond::Nc),
+        "cf" => Operand::Cond(Cond::C),
+        n if operator == "rst" => {
+            let vector = n.trim_start_matches("0x");
+            Operand::Target(u16::from_str_radix(vector, 16).expect("Bad vector"))
+        }
+        // `stop` is followed by a padding byte.
+        "0" if operator == "stop" => Operand::Imm8(d8()),
+        n if n.as_bytes()[0].is_ascii_digit() => Operand::Bit(n.parse().expect("Bad bit")),
+        r => register(r),
+    }
+}
+
+/// Decode the instruction at the beginning of the byte slice, which is located at `addr`.
+///
+/// Returns `None` if the opcode is invalid or the slice is shorter than the instruction.
+pub fn decode(bytes: &[u8], addr: u16) -> Option<Instruction> {
+    let code = match *bytes.first()? {
+        0xcb => 0xcb00 | *bytes.get(1)? as u16,
+        b => b as u16,
+    };
+
+    let attrs = attrs(code)?;
+    let bytes = bytes.get(..attrs.size)?;
+    let arg = if code > 0xff {
+        &bytes[2..]
+    } else {
+        &bytes[1..]
+    };
+
+    let operands = attrs
+        .operands
+        .iter()
+        .map(|t| operand(attrs.operator, t, addr, arg))
+        .collect();
+
+    Some(Instruction {
+        addr,
+        code,
+        operator: attrs.operator,
+        operands,
+        len: attrs.size,
+        cycles: attrs.time,
+        flags: [attrs.z, attrs.n, attrs.h, attrs.c],
+    })
+}
+
+/// Decode the instruction at `addr` reading the memory with `read`.
+///
+/// Returns `None` if the opcode is invalid.
+pub fn decode_at<F: FnMut(u16) -> u8>(mut read: F, addr: u16) -> Option<Instruction> {
+    let mut bytes = [0; 3];
+
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = read(addr.wrapping_add(i as u16));
+    }
+
+    decode(&bytes, addr)
+}
+
+/// Iterator which decodes the instructions in the byte slice one after another.
+pub struct Disassembler<'a> {
+    bytes: &'a [u8],
+    addr: u16,
+}
+
+impl<'a> Disassembler<'a> {
+    /// Create a disassembler for the byte slice which is located at `addr`.
+    pub fn new(bytes: &'a [u8], addr: u16) -> Self {
+        Self { bytes, addr }
+    }
+}
+
+impl Iterator for Disassembler<'_> {
+    /// The decoded instruction, or the address and the byte which can't be decoded.
+    type Item = Result<Instruction, (u16, u8)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let b = *self.bytes.first()?;
+
+        let (item, len) = match decode(self.bytes, self.addr) {
+            Some(inst) => {
+                let len = inst.len;
+                (Ok(inst), len)
+            }
+            None => (Err((self.addr, b)), 1),
+        };
+
+        self.bytes = &self.bytes[len..];
+        self.addr = self.addr.wrapping_add(len as u16);
+
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn operands() {
+        let inst = decode(&[0x01, 0x34, 0x12], 0x100).unwrap();
+        assert_eq!(inst.operator, "ld");
+        assert_eq!(
+            inst.operands,
+            vec![Operand::Reg("bc"), Operand::Imm16(0x1234)]
+        );
+        assert_eq!(inst.len, 3);
+        assert_eq!(inst.next(), 0x103);
+        assert_eq!(inst.to_string(), "ld bc,0x1234");
+
+        let inst = decode(&[0xe0, 0x47], 0).unwrap();
+        assert_eq!(inst.to_string(), "ld (0xff47),a");
+
+        let inst = decode(&[0xf2], 0).unwrap();
+        assert_eq!(inst.to_string(), "ld a,(0xff00+c)");
+
+        let inst = decode(&[0xf8, 0xfe], 0).unwrap();
+        assert_eq!(inst.to_string(), "ldhl sp,-2");
+        assert_eq!(
+            inst.flags,
+            [
+                FlagEffect::Reset,
+                FlagEffect::Reset,
+                FlagEffect::Affected,
+                FlagEffect::Affected
+            ]
+        );
+
+        let inst = decode(&[0xcb, 0x46], 0).unwrap();
+        assert_eq!(inst.code, 0xcb46);
+        assert_eq!(inst.to_string(), "bit 0,(hl)");
+        assert_eq!(inst.len, 2);
+        assert_eq!(inst.cycles, (12, 12));
+        assert_eq!(inst.flags[1], FlagEffect::Reset);
+        assert_eq!(inst.flags[2], FlagEffect::Set);
+        assert_eq!(inst.flags[3], FlagEffect::Unaffected);
+    }
+
+    #[test]
+    fn branches() {
+        let inst = decode(&[0x20, 0xfe], 0xc004).unwrap();
+        assert_eq!(inst.to_string(), "jr nz,0xc004");
+        assert_eq!(inst.target(), Some(0xc004));
+        assert!(inst.is_conditional());
+        assert_eq!(inst.cycles, (12, 8));
+
+        let inst = decode(&[0xcd, 0x10, 0xc0], 0xc000).unwrap();
+        assert_eq!(inst.to_string(), "call 0xc010");
+        assert_eq!(inst.target(), Some(0xc010));
+        assert!(!inst.is_conditional());
+
+        let inst = decode(&[0xff], 0).unwrap();
+        assert_eq!(inst.to_string(), "rst 0x0038");
+        assert_eq!(inst.target(), Some(0x38));
+
+        let inst = decode(&[0xe9], 0).unwrap();
+        assert_eq!(inst.to_string(), "jp hl");
+        assert_eq!(inst.target(), None);
+    }
+
+    #[test]
+    fn symbolic() {
+        let syms = Symbols::parse("00:0150 Start\n02:4000 Func\n00:c000 wCounter\n").unwrap();
+
+        let inst = decode(&[0xcd, 0x03, 0x40], 0x150).unwrap();
+        assert_eq!(inst.symbolic(&syms, 2).to_string(), "call Func+0x3");
+        assert_eq!(inst.symbolic(&syms, 3).to_string(), "call 0x4003");
+
+        let inst = decode(&[0x18, 0xfe], 0x150).unwrap();
+        assert_eq!(inst.symbolic(&syms, 1).to_string(), "jr Start");
+
+        let inst = decode(&[0xea, 0x00, 0xc0], 0x150).unwrap();
+        assert_eq!(inst.symbolic(&syms, 1).to_string(), "ld (wCounter),a");
+
+        let inst = decode(&[0xe0, 0x47], 0x150).unwrap();
+        assert_eq!(inst.symbolic(&syms, 1).to_string(), "ld (0xff47),a");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(decode(&[], 0), None);
+        assert_eq!(decode(&[0xd3], 0), None);
+        assert_eq!(decode(&[0xcb], 0), None);
+        assert_eq!(decode(&[0xc3, 0x00], 0), None);
+    }
+
+    #[test]
+    fn memory_view() {
+        let mem = [0x00, 0xc3, 0x50, 0x01];
+        let inst = decode_at(|addr| mem[addr as usize % mem.len()], 1).unwrap();
+        assert_eq!(inst.to_string(), "jp 0x0150");
+    }
+
+    #[test]
+    fn listing() {
+        let list: Vec<_> = Disassembler::new(&[0x00, 0xd3, 0x3e, 0x01, 0xc9], 0x200)
+            .map(|r| r.map(|inst| (inst.addr, inst.to_string())))
+            .collect();
+
+        assert_eq!(
+            list,
+            vec![
+                Ok((0x200, "nop".to_string())),
+                Err((0x201, 0xd3)),
+                Ok((0x202, "ld a,0x01".to_string())),
+                Ok((0x204, "ret".to_string())),
+            ]
+        );
+    }
+}
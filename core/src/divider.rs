@@ -1,42 +1,33 @@
-use crate::clock::PrescaledTimer;
-
+/// The 16-bit system counter, whose upper 8 bits are visible as DIV register.
+/// The timer is also clocked by the falling edges of this counter.
 pub struct Divider {
-    timer: PrescaledTimer,
+    counter: u16,
 }
 
 impl Divider {
     pub fn new() -> Self {
-        Self {
-            timer: PrescaledTimer::builder()
-                .enable()
-                .frequency(16384)
-                .interval(256)
-                .build(),
-        }
+        Self { counter: 0 }
     }
 
-    pub fn step(&mut self, cycles: usize) -> usize {
-        self.timer.step(cycles);
-        self.timer.counter()
+    pub fn step(&mut self, cycles: usize) {
+        self.counter = self.counter.wrapping_add(cycles as u16);
     }
 
-    // TODO: To be used for STOP emulation where DIV doesn't ticks
-    #[allow(dead_code)]
-    fn enable(&mut self) {
-        self.timer.enable();
+    /// The value of DIV register.
+    pub fn counter(&self) -> usize {
+        (self.counter >> 8) as usize
     }
 
-    // TODO: To be used for STOP emulation where DIV doesn't ticks
-    #[allow(dead_code)]
-    fn diable(&mut self) {
-        self.timer.disable();
+    /// The whole 16-bit system counter.
+    pub fn system_counter(&self) -> u16 {
+        self.counter
     }
 
     pub fn on_read(&self) -> u8 {
-        self.timer.counter() as u8
+        self.counter() as u8
     }
 
     pub fn on_write(&mut self, _value: u8) {
-        self.timer.set_counter(0);
+        self.counter = 0;
     }
 }
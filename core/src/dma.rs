@@ -24,48 +24,110 @@ impl DmaRequest {
     }
 }
 
+/// Memory bus used by OAM DMA, which conflicts with the CPU access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bus {
+    /// Cartridge ROM/RAM and WRAM.
+    External,
+    /// VRAM.
+    Vram,
+}
+
+impl Bus {
+    /// The bus which the address belongs to, if any.
+    fn of(addr: u16) -> Option<Bus> {
+        match addr {
+            0x8000..=0x9fff => Some(Bus::Vram),
+            0x0000..=0x7fff | 0xa000..=0xfdff => Some(Bus::External),
+            _ => None,
+        }
+    }
+}
+
+/// OAM DMA
 pub struct Dma {
+    /// Source address of the current transfer.
     src: u16,
-    dst: u16,
-    cycles: usize,
+    /// Number of bytes transferred so far.
+    pos: u16,
+    /// Set while the transfer is in progress.
+    running: bool,
+    /// Transfer waiting for the startup delay (source address, machine cycles).
+    pending: Option<(u16, usize)>,
+    /// The byte transferred last, which the CPU sees on bus conflicts.
+    value: u8,
 }
 
 impl Dma {
     pub fn new() -> Self {
         Self {
             src: 0,
-            dst: 0,
-            cycles: 0,
+            pos: 0,
+            running: false,
+            pending: None,
+            value: 0xff,
         }
     }
 
-    pub fn step(&mut self, cycles: usize) -> Option<DmaRequest> {
-        if self.cycles == 0 {
-            return None;
+    /// Run the transfer by one machine cycle.
+    /// Returns the source and destination address of the byte to be copied in the cycle.
+    pub fn tick(&mut self) -> Option<(u16, u16)> {
+        match self.pending {
+            Some((src, 0)) => {
+                // Restarting replaces the transfer in progress.
+                self.pending = None;
+                self.src = src;
+                self.pos = 0;
+                self.running = true;
+            }
+            Some((src, delay)) => self.pending = Some((src, delay - 1)),
+            None => {}
         }
 
-        // Ensure cpu cycles = machine cycles * 4
-        assert!(cycles % 4 == 0);
-        assert!(self.cycles % 4 == 0);
+        if !self.running {
+            return None;
+        }
 
-        // Copy 1 byte per a machine cycle
-        let len = (cycles / 4).min(self.cycles / 4) as u16;
-        let req = DmaRequest::new(self.src, self.dst, len);
+        let req = (self.src + self.pos, 0xfe00 + self.pos);
 
-        self.src += len;
-        self.dst += len;
-        self.cycles = self.cycles.saturating_sub(cycles);
+        self.pos += 1;
+        if self.pos == 0xa0 {
+            self.running = false;
+        }
 
         Some(req)
     }
 
+    /// Record the byte transferred in the current cycle.
+    pub fn transfer(&mut self, value: u8) {
+        self.value = value;
+    }
+
+    /// Check if the transfer is in progress.
+    pub fn active(&self) -> bool {
+        self.running
+    }
+
+    /// Check if the CPU access to the address conflicts with the transfer.
+    pub fn conflict(&self, addr: u16) -> bool {
+        self.running && Bus::of(addr).is_some() && Bus::of(addr) == Bus::of(self.src)
+    }
+
+    /// The byte on the bus used by the transfer.
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+
     /// Write DMA register (0xff46)
     pub fn start(&mut self, value: u8) {
-        assert!(value <= 0xdf);
-        self.cycles = 160 * 4; // 160 machine cycles (* 4 for cpu cycles)
-        self.src = (value as u16) << 8;
-        self.dst = 0xfe00;
-        debug!("Start DMA transfer: {:02x} to {:02x}", self.src, self.dst);
+        let src = (value as u16) << 8;
+        // 0xe000 - 0xffff is mapped to WRAM.
+        let src = if src >= 0xe000 { src - 0x2000 } else { src };
+
+        // The transfer starts after 1 machine cycle.
+        self.pending = Some((src, 1));
+
+        debug!("Start DMA transfer: {:04x} to fe00", src);
     }
 
     /// Read DMA register (0xff46)
@@ -74,3 +136,64 @@ impl Dma {
         0xff
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dma() {
+        let mut dma = Dma::new();
+
+        dma.start(0xc1);
+        assert_eq!(dma.tick(), None);
+        assert!(!dma.active());
+
+        for i in 0..0xa0 {
+            assert_eq!(dma.tick(), Some((0xc100 + i, 0xfe00 + i)));
+            assert!(dma.active() || i == 0x9f);
+        }
+        assert_eq!(dma.tick(), None);
+        assert!(!dma.active());
+    }
+
+    #[test]
+    fn test_dma_mirror() {
+        let mut dma = Dma::new();
+
+        dma.start(0xfe);
+        dma.tick();
+        assert_eq!(dma.tick(), Some((0xde00, 0xfe00)));
+    }
+
+    #[test]
+    fn test_dma_restart() {
+        let mut dma = Dma::new();
+
+        dma.start(0x80);
+        dma.tick();
+        for _ in 0..0x10 {
+            dma.tick();
+        }
+
+        // The old transfer continues during the startup delay.
+        dma.start(0x40);
+        assert_eq!(dma.tick(), Some((0x8010, 0xfe10)));
+        assert_eq!(dma.tick(), Some((0x4000, 0xfe00)));
+    }
+
+    #[test]
+    fn test_dma_conflict() {
+        let mut dma = Dma::new();
+
+        dma.start(0xc0);
+        dma.tick();
+        assert!(!dma.conflict(0x1234));
+
+        dma.tick();
+        assert!(dma.conflict(0x1234));
+        assert!(dma.conflict(0xa000));
+        assert!(!dma.conflict(0x8000));
+        assert!(!dma.conflict(0xff80));
+    }
+}
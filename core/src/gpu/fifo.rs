@@ -0,0 +1,358 @@
+use super::{Color, Gpu, Sprite};
+use crate::hardware::VRAM_WIDTH;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+/// Pixel in the background/window FIFO.
+#[derive(Clone, Copy, Debug, Default)]
+struct BgPixel {
+    coli: usize,
+    palette: usize,
+    priority: bool,
+}
+
+/// Pixel in the sprite FIFO.
+#[derive(Clone, Copy, Debug, Default)]
+struct ObjPixel {
+    coli: usize,
+    attr: u8,
+    index: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FetchStep {
+    Tile,
+    DataLow,
+    DataHigh,
+    Push,
+}
+
+/// State of the pixel FIFO renderer for the current line.
+pub(super) struct Fifo {
+    bg: VecDeque<BgPixel>,
+    obj: VecDeque<ObjPixel>,
+    step: FetchStep,
+    step_dots: usize,
+    fetch_x: u16,
+    tile_base: u16,
+    tile_attr: u8,
+    tile_row: u16,
+    tile_low: u8,
+    tile_high: u8,
+    window: bool,
+    warmup: usize,
+    discard: usize,
+    lx: usize,
+    sprites: Vec<Sprite>,
+    sprite_fetch: Option<(Sprite, usize)>,
+    dots: usize,
+    buf: Vec<u32>,
+}
+
+impl Fifo {
+    pub(super) fn new() -> Self {
+        Self {
+            bg: VecDeque::with_capacity(16),
+            obj: VecDeque::with_capacity(8),
+            step: FetchStep::Tile,
+            step_dots: 0,
+            fetch_x: 0,
+            tile_base: 0,
+            tile_attr: 0,
+            tile_row: 0,
+            tile_low: 0,
+            tile_high: 0,
+            window: false,
+            warmup: 0,
+            discard: 0,
+            lx: 0,
+            sprites: Vec::new(),
+            sprite_fetch: None,
+            dots: 0,
+            buf: vec![0; VRAM_WIDTH],
+        }
+    }
+
+    /// The number of dots spent in mode 3 so far.
+    pub(super) fn dots(&self) -> usize {
+        self.dots
+    }
+
+    /// Check if the window is rendered in the current line.
+    pub(super) fn window(&self) -> bool {
+        self.window
+    }
+
+    /// The pixels rendered in the current line.
+    pub(super) fn buf(&self) -> &[u32] {
+        &self.buf
+    }
+}
+
+impl Gpu {
+    /// Start rendering the current line with the pixel FIFO renderer.
+    /// Called at the beginning of mode 3.
+    pub(super) fn fifo_start(&mut self) {
+        let mut sprites = self.scan_oam();
+
+        // The sprites are fetched from left to right while the pixels are shifted out.
+        sprites.sort_by_key(|sp| sp.xpos);
+
+        self.fifo = Fifo {
+            sprites,
+            // The first tile is fetched twice, which delays mode 3 by 6 dots.
+            warmup: 6,
+            // The pixels scrolled out by SCX are discarded at the beginning of the line.
+            discard: self.scx as usize % 8,
+            ..Fifo::new()
+        };
+    }
+
+    /// Run the pixel FIFO renderer by the given dots.
+    /// Returns the remaining dots if the line is completed.
+    pub(super) fn fifo_run(&mut self, dots: usize) -> Option<usize> {
+        for i in 0..dots {
+            if self.fifo_dot() {
+                return Some(dots - i - 1);
+            }
+        }
+        None
+    }
+
+    /// Run the pixel FIFO renderer by one dot. Returns `true` when the line is completed.
+    fn fifo_dot(&mut self) -> bool {
+        self.fifo.dots += 1;
+
+        if self.fifo.warmup > 0 {
+            self.fifo.warmup -= 1;
+            return false;
+        }
+
+        if let Some((sp, dots)) = self.fifo.sprite_fetch {
+            if dots == 0 {
+                // The sprite fetch waits for the background fetcher to finish the current tile.
+                if self.fifo.step == FetchStep::Push {
+                    self.fifo.sprite_fetch = Some((sp, 5));
+                } else {
+                    self.fifo_fetch();
+                }
+            } else if dots == 1 {
+                self.fifo_fetch_sprite(sp);
+                self.fifo.sprite_fetch = None;
+            } else {
+                self.fifo.sprite_fetch = Some((sp, dots - 1));
+            }
+            return false;
+        }
+
+        self.fifo_fetch();
+
+        if !self.fifo.window {
+            if let Some(winx) = self.window_x() {
+                if self.fifo.lx as i16 >= winx.max(0) && (self.fifo.discard == 0 || winx < 0) {
+                    // Window starts; the background pixels are thrown away and the fetcher restarts.
+                    self.fifo.window = true;
+                    self.fifo.bg.clear();
+                    self.fifo.fetch_x = 0;
+                    self.fifo.step = FetchStep::Tile;
+                    self.fifo.step_dots = 0;
+                    // The window pixels left to the screen are discarded.
+                    self.fifo.discard = (-winx).max(0) as usize;
+                    return false;
+                }
+            }
+        }
+
+        if self.spenable {
+            let lx = self.fifo.lx;
+            if let Some(i) = self
+                .fifo
+                .sprites
+                .iter()
+                .position(|sp| sp.xpos as usize <= lx + 8)
+            {
+                let sp = self.fifo.sprites.remove(i);
+                self.fifo.sprite_fetch = Some((sp, 0));
+                return false;
+            }
+        }
+
+        let bg = match self.fifo.bg.pop_front() {
+            Some(bg) => bg,
+            None => return false,
+        };
+
+        if self.fifo.discard > 0 {
+            self.fifo.discard -= 1;
+            return false;
+        }
+
+        let obj = self.fifo.obj.pop_front();
+
+        self.fifo.buf[self.fifo.lx] = self.fifo_mix(bg, obj);
+        self.fifo.lx += 1;
+
+        self.fifo.lx == VRAM_WIDTH
+    }
+
+    /// Run the background/window fetcher by one dot.
+    fn fifo_fetch(&mut self) {
+        if self.fifo.step == FetchStep::Push {
+            if self.fifo.bg.is_empty() {
+                self.fifo_push_tile();
+                self.fifo.fetch_x += 1;
+                self.fifo.step = FetchStep::Tile;
+            }
+            return;
+        }
+
+        // Each fetch step takes 2 dots.
+        self.fifo.step_dots += 1;
+        if self.fifo.step_dots < 2 {
+            return;
+        }
+        self.fifo.step_dots = 0;
+
+        let bank = if self.fifo.tile_attr & 0x08 != 0 {
+            1
+        } else {
+            0
+        };
+
+        match self.fifo.step {
+            FetchStep::Tile => {
+                let (mapbase, tx, ty, row) = if self.fifo.window {
+                    let yy = self.win_line as u16;
+                    (self.winmap, self.fifo.fetch_x & 0x1f, yy / 8, yy % 8)
+                } else {
+                    let yy = (self.ly as u16 + self.scy as u16) % 256;
+                    let tx = (self.scx as u16 / 8 + self.fifo.fetch_x) & 0x1f;
+                    (self.bgmap, tx, yy / 8, yy % 8)
+                };
+
+                self.fifo.tile_base = self.get_tile_base(mapbase, tx, ty);
+                self.fifo.tile_attr = if self.color {
+                    self.read_vram_bank(mapbase + tx + ty * 32, 1)
+                } else {
+                    0
+                };
+                self.fifo.tile_row = if self.fifo.tile_attr & 0x40 != 0 {
+                    7 - row
+                } else {
+                    row
+                };
+                self.fifo.step = FetchStep::DataLow;
+            }
+            FetchStep::DataLow => {
+                let addr = self.fifo.tile_base + self.fifo.tile_row * 2;
+                self.fifo.tile_low = self.read_vram_bank(addr, bank);
+                self.fifo.step = FetchStep::DataHigh;
+            }
+            FetchStep::DataHigh => {
+                let addr = self.fifo.tile_base + self.fifo.tile_row * 2 + 1;
+                self.fifo.tile_high = self.read_vram_bank(addr, bank);
+                self.fifo.step = FetchStep::Push;
+            }
+            FetchStep::Push => unreachable!(),
+        }
+    }
+
+    fn fifo_push_tile(&mut self) {
+        let attr = self.fifo.tile_attr;
+
+        for i in 0..8 {
+            let bit = if attr & 0x20 != 0 { i } else { 7 - i };
+            let coli = tile_pixel(self.fifo.tile_low, self.fifo.tile_high, bit);
+
+            self.fifo.bg.push_back(BgPixel {
+                coli,
+                palette: attr as usize & 0x7,
+                priority: attr & 0x80 != 0,
+            });
+        }
+    }
+
+    /// Fetch the sprite and mix it into the sprite FIFO.
+    fn fifo_fetch_sprite(&mut self, sp: Sprite) {
+        let ly = self.ly as u16;
+        let yflip = sp.attr & 0x40 != 0;
+        let xflip = sp.attr & 0x20 != 0;
+
+        // LCDC may be changed after OAM scan; wrap around the sprite height.
+        let tyoff = (ly + 16 - sp.ypos) & (self.spsize - 1);
+        let tyoff = if yflip {
+            self.spsize - 1 - tyoff
+        } else {
+            tyoff
+        };
+        let ti = if self.spsize == 16 {
+            if tyoff >= 8 {
+                sp.ti | 1
+            } else {
+                sp.ti & 0xfe
+            }
+        } else {
+            sp.ti
+        };
+        let tyoff = tyoff % 8;
+
+        let bank = if self.color && sp.attr & 0x08 != 0 {
+            1
+        } else {
+            0
+        };
+        let tbase = 0x8000 + ti * 16;
+        let low = self.read_vram_bank(tbase + tyoff * 2, bank);
+        let high = self.read_vram_bank(tbase + tyoff * 2 + 1, bank);
+
+        while self.fifo.obj.len() < 8 {
+            self.fifo.obj.push_back(ObjPixel::default());
+        }
+
+        // The pixels left to the screen are clipped.
+        let skip = (self.fifo.lx + 8).saturating_sub(sp.xpos as usize);
+
+        for i in skip..8 {
+            let bit = if xflip { i } else { 7 - i };
+            let coli = tile_pixel(low, high, bit);
+
+            let slot = &mut self.fifo.obj[i - skip];
+
+            // On DMG, the sprite fetched first wins. On CGB, the smaller OAM index wins.
+            if coli != 0 && (slot.coli == 0 || (self.color && sp.index < slot.index)) {
+                *slot = ObjPixel {
+                    coli,
+                    attr: sp.attr,
+                    index: sp.index,
+                };
+            }
+        }
+    }
+
+    /// Mix the background pixel and the sprite pixel into the final color.
+    fn fifo_mix(&self, bg: BgPixel, obj: Option<ObjPixel>) -> u32 {
+        let (bgcoli, bgcol) = if self.color {
+            (bg.coli, self.bg_color_palette.cols[bg.palette][bg.coli])
+        } else if self.bgenable {
+            (bg.coli, self.bg_palette[bg.coli])
+        } else {
+            // Background is blank when disabled.
+            (0, Color::White)
+        };
+
+        match obj {
+            Some(obj)
+                if obj.coli != 0
+                    && !self.bg_over_obj(bgcoli, bg.priority, obj.attr & 0x80 != 0) =>
+            {
+                self.get_sp_attr(obj.attr).palette[obj.coli].into()
+            }
+            _ => bgcol.into(),
+        }
+    }
+}
+
+fn tile_pixel(low: u8, high: u8, bit: usize) -> usize {
+    let l = (low >> bit) & 1;
+    let h = ((high >> bit) & 1) << 1;
+    (h | l) as usize
+}
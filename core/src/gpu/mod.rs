@@ -0,0 +1,1689 @@
+mod fifo;
+
+use self::fifo::Fifo;
+use crate::dma::DmaRequest;
+use crate::hardware::{HardwareHandle, VRAM_HEIGHT, VRAM_WIDTH};
+use crate::ic::Irq;
+use alloc::{vec, vec::Vec};
+use log::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Oam,
+    Vram,
+    HBlank,
+    VBlank,
+    None,
+}
+
+impl From<Mode> for u8 {
+    fn from(v: Mode) -> u8 {
+        match v {
+            Mode::HBlank => 0,
+            Mode::VBlank => 1,
+            Mode::Oam => 2,
+            Mode::Vram => 3,
+            Mode::None => 0,
+        }
+    }
+}
+
+impl From<u8> for Mode {
+    fn from(v: u8) -> Mode {
+        match v {
+            0 => Mode::HBlank,
+            1 => Mode::VBlank,
+            2 => Mode::Oam,
+            3 => Mode::Vram,
+            _ => Mode::None,
+        }
+    }
+}
+
+pub struct Gpu {
+    color: bool,
+    sprite_limit: bool,
+    pixel_fifo: bool,
+
+    irq: Irq,
+
+    clocks: usize,
+    hblank_clocks: usize,
+
+    lyc_interrupt: bool,
+    oam_interrupt: bool,
+    vblank_interrupt: bool,
+    hblank_interrupt: bool,
+    /// STAT interrupt line, which is the OR of all the enabled STAT sources.
+    stat_line: bool,
+    mode: Mode,
+    /// Set in the first line after the LCD is turned on, which doesn't have mode 2.
+    first_line: bool,
+    /// Set in the first frame after the LCD is turned on, which isn't displayed.
+    skip_frame: bool,
+
+    ly: u8,
+    lyc: u8,
+    scy: u8,
+    scx: u8,
+
+    wx: u8,
+    wy: u8,
+    /// Set once LY matches WY in the current frame.
+    wy_latch: bool,
+    /// Internal window line counter.
+    win_line: u8,
+    /// Set if the window spans the whole next line because of WX=166.
+    win_wrap: bool,
+
+    enable: bool,
+    winmap: u16,
+    winenable: bool,
+    tiles: u16,
+    bgmap: u16,
+    spsize: u16,
+    spenable: bool,
+    bgenable: bool,
+    hw: HardwareHandle,
+
+    bg_palette: Vec<Color>,
+    obj_palette0: Vec<Color>,
+    obj_palette1: Vec<Color>,
+    bg_color_palette: ColorPalette,
+    obj_color_palette: ColorPalette,
+    vram: Vec<Vec<u8>>,
+    vram_select: usize,
+
+    oam: Vec<u8>,
+
+    hdma: Hdma,
+
+    fifo: Fifo,
+}
+
+fn to_palette(p: u8) -> Vec<Color> {
+    vec![
+        (p & 0x3).into(),
+        ((p >> 2) & 0x3).into(),
+        ((p >> 4) & 0x3).into(),
+        ((p >> 6) & 0x3).into(),
+    ]
+}
+
+fn from_palette(p: Vec<Color>) -> u8 {
+    assert_eq!(p.len(), 4);
+
+    u8::from(p[0]) | u8::from(p[1]) << 2 | u8::from(p[2]) << 4 | u8::from(p[3]) << 6
+}
+
+/// Sprite entry in OAM.
+#[derive(Clone, Copy, Debug)]
+struct Sprite {
+    index: usize,
+    ypos: u16,
+    xpos: u16,
+    ti: u16,
+    attr: u8,
+}
+
+struct MapAttribute<'a> {
+    palette: &'a [Color],
+    vram_bank: usize,
+    xflip: bool,
+    yflip: bool,
+    priority: bool,
+}
+
+struct ColorPalette {
+    cols: Vec<Vec<Color>>,
+    index: usize,
+    auto_inc: bool,
+}
+
+impl ColorPalette {
+    fn new() -> Self {
+        Self {
+            cols: vec![vec![Color::rgb(); 4]; 8],
+            index: 0,
+            auto_inc: false,
+        }
+    }
+
+    fn select(&mut self, value: u8) {
+        self.auto_inc = value & 0x80 != 0;
+        self.index = value as usize & 0x3f;
+    }
+
+    fn selected(&self) -> u8 {
+        // Bit 6 is unused and always reads 1.
+        (self.auto_inc as u8) << 7 | 0x40 | self.index as u8
+    }
+
+    fn read(&self) -> u8 {
+        let idx = self.index / 8;
+        let off = self.index % 8;
+
+        if off % 2 == 0 {
+            self.cols[idx][off / 2].get_low()
+        } else {
+            self.cols[idx][off / 2].get_high()
+        }
+    }
+
+    fn write(&mut self, value: u8) {
+        let idx = self.index / 8;
+        let off = self.index % 8;
+
+        if off % 2 == 0 {
+            self.cols[idx][off / 2].set_low(value)
+        } else {
+            self.cols[idx][off / 2].set_high(value)
+        }
+
+        if self.auto_inc {
+            self.index = (self.index + 1) % 0x40;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Color {
+    White,
+    LightGray,
+    DarkGray,
+    Black,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn rgb() -> Self {
+        Color::Rgb(0, 0, 0)
+    }
+
+    fn set_low(&mut self, low: u8) {
+        match *self {
+            Color::Rgb(_, g, b) => {
+                let nr = low & 0x1f;
+                let ng = g & !0x7 | low >> 5;
+                *self = Color::Rgb(nr, ng, b);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_high(&mut self, high: u8) {
+        match *self {
+            Color::Rgb(r, g, _) => {
+                let ng = g & !0x18 | (high & 0x3) << 3;
+                let nb = (high >> 2) & 0x1f;
+                *self = Color::Rgb(r, ng, nb);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_low(&self) -> u8 {
+        match *self {
+            Color::Rgb(r, g, _) => (r & 0x1f) | (g & 0x7) << 5,
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_high(&self) -> u8 {
+        match *self {
+            Color::Rgb(_, g, b) => ((g >> 3) & 0x3) | (b & 0x1f) << 2,
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn color_adjust(v: u8) -> u32 {
+    let v = v as u32;
+
+    if v >= 0x10 {
+        0xff - (0x1f - v)
+    } else {
+        v
+    }
+}
+
+impl From<Color> for u32 {
+    fn from(c: Color) -> u32 {
+        match c {
+            Color::White => 0xdddddd,
+            Color::LightGray => 0xaaaaaa,
+            Color::DarkGray => 0x888888,
+            Color::Black => 0x555555,
+            Color::Rgb(r, g, b) => {
+                let mut c = 0;
+                c |= color_adjust(r) << 16;
+                c |= color_adjust(g) << 8;
+                c |= color_adjust(b);
+                c
+            }
+        }
+    }
+}
+
+impl From<Color> for u8 {
+    fn from(c: Color) -> u8 {
+        match c {
+            Color::White => 0,
+            Color::LightGray => 1,
+            Color::DarkGray => 2,
+            Color::Black => 3,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<u8> for Color {
+    fn from(v: u8) -> Color {
+        match v {
+            0 => Color::White,
+            1 => Color::LightGray,
+            2 => Color::DarkGray,
+            3 => Color::Black,
+            _ => unreachable!(),
+        }
+    }
+}
+
+struct Hdma {
+    on: bool,
+    src_low: u8,
+    src_high: u8,
+    dst_low: u8,
+    dst_high: u8,
+    src_wip: u16,
+    dst_wip: u16,
+    len: u8,
+    hblank: bool,
+    /// Bytes left to copy in the block being transferred.
+    block: u16,
+    /// Dots not consumed by the transfer yet.
+    clocks: usize,
+}
+
+impl Hdma {
+    fn new() -> Self {
+        Self {
+            on: false,
+            src_low: 0,
+            src_high: 0,
+            dst_low: 0,
+            dst_high: 0,
+            src_wip: 0,
+            dst_wip: 0,
+            len: 0x7f,
+            hblank: false,
+            block: 0,
+            clocks: 0,
+        }
+    }
+
+    /// Write HDMA5 register (0xff55)
+    fn start(&mut self, value: u8) {
+        if self.on && self.hblank && value & 0x80 == 0 {
+            self.on = false;
+            self.hblank = false;
+
+            debug!("Cancel HDMA transfer");
+        } else {
+            self.hblank = value & 0x80 != 0;
+            self.len = value & 0x7f;
+            self.src_wip = ((self.src_high as u16) << 8 | self.src_low as u16) & !0x000f;
+            self.dst_wip = ((self.dst_high as u16) << 8 | self.dst_low as u16) & !0xe00f | 0x8000;
+            self.on = true;
+
+            info!(
+                "Start HDMA transfer: {:04x} -> {:04x} ({}) {}",
+                self.src_wip, self.dst_wip, self.len, self.hblank
+            );
+
+            if !self.hblank {
+                // General mode starts immediately.
+                self.trigger();
+            }
+        }
+    }
+
+    /// Read HDMA5 register (0xff55)
+    fn status(&self) -> u8 {
+        // Bit 7 is cleared while the transfer is active.
+        // The remaining length reads 0x7f after the transfer completes.
+        self.len | if self.on { 0x00 } else { 0x80 }
+    }
+
+    /// Start transferring the next block of 16 bytes.
+    fn trigger(&mut self) {
+        if self.on && self.block == 0 {
+            self.block = 0x10;
+        }
+    }
+
+    /// Check if a block is being transferred, which stalls the CPU.
+    fn active(&self) -> bool {
+        self.block > 0
+    }
+
+    fn run(&mut self, time: usize, hblank: bool) -> Option<DmaRequest> {
+        // H-blank mode copies one block in each hblank.
+        if self.hblank && hblank {
+            self.trigger();
+        }
+
+        if self.block == 0 {
+            return None;
+        }
+
+        // Copies 1 byte per 2 dots; 16 bytes take 8 machine cycles in normal speed,
+        // and 16 machine cycles in double speed.
+        self.clocks += time;
+        let size = ((self.clocks / 2) as u16).min(self.block);
+        self.clocks -= size as usize * 2;
+        self.block -= size;
+
+        let req = DmaRequest::new(self.src_wip, self.dst_wip, size);
+
+        // Blocks are aligned to 16 bytes, so they never cross the wraparound boundary.
+        self.src_wip = self.src_wip.wrapping_add(size);
+        self.dst_wip = (self.dst_wip + size) & 0x1fff | 0x8000;
+
+        if self.block == 0 {
+            self.clocks = 0;
+
+            let (rem, of) = self.len.overflowing_sub(1);
+            self.len = rem & 0x7f;
+
+            if of {
+                self.on = false;
+                self.hblank = false;
+            } else if !self.hblank {
+                // General mode copies all the blocks at once.
+                self.trigger();
+            }
+        }
+
+        if size > 0 {
+            Some(req)
+        } else {
+            None
+        }
+    }
+}
+
+impl Gpu {
+    pub fn new(
+        hw: HardwareHandle,
+        irq: Irq,
+        color: bool,
+        sprite_limit: bool,
+        pixel_fifo: bool,
+    ) -> Self {
+        Self {
+            color,
+            sprite_limit,
+            pixel_fifo,
+            irq,
+            clocks: 0,
+            hblank_clocks: 204,
+            lyc_interrupt: false,
+            oam_interrupt: false,
+            vblank_interrupt: false,
+            hblank_interrupt: false,
+            stat_line: false,
+            first_line: false,
+            skip_frame: false,
+            mode: Mode::None,
+            ly: 0,
+            lyc: 0,
+            scy: 0,
+            scx: 0,
+            wx: 0,
+            wy: 0,
+            wy_latch: false,
+            win_line: 0,
+            win_wrap: false,
+            enable: false,
+            winmap: 0x9800,
+            winenable: false,
+            tiles: 0x8800,
+            bgmap: 0x9800,
+            spsize: 8,
+            spenable: false,
+            bgenable: false,
+            hw,
+            bg_palette: vec![
+                Color::White,
+                Color::LightGray,
+                Color::DarkGray,
+                Color::Black,
+            ],
+            obj_palette0: vec![
+                Color::White,
+                Color::LightGray,
+                Color::DarkGray,
+                Color::Black,
+            ],
+            obj_palette1: vec![
+                Color::White,
+                Color::LightGray,
+                Color::DarkGray,
+                Color::Black,
+            ],
+            bg_color_palette: ColorPalette::new(),
+            obj_color_palette: ColorPalette::new(),
+            vram: vec![vec![0; 0x2000]; 2],
+            vram_select: 0,
+            oam: vec![0; 0xa0],
+            hdma: Hdma::new(),
+            fifo: Fifo::new(),
+        }
+    }
+
+    pub fn step(&mut self, time: usize) -> Option<DmaRequest> {
+        let clocks = self.clocks + time;
+
+        let (clocks, mode) = match &self.mode {
+            Mode::Oam => {
+                if clocks >= 80 {
+                    self.first_line = false;
+
+                    if self.ly == self.wy {
+                        self.wy_latch = true;
+                    }
+
+                    if self.pixel_fifo {
+                        self.fifo_start();
+                    }
+
+                    (clocks - 80, Mode::Vram)
+                } else {
+                    (clocks, Mode::Oam)
+                }
+            }
+            Mode::Vram => {
+                if self.pixel_fifo {
+                    match self.fifo_run(clocks) {
+                        Some(clocks) => {
+                            self.vram_update(self.fifo.buf());
+
+                            self.window_line_done(self.fifo.window());
+
+                            // Mode 3 and HBlank takes 376 dots in total.
+                            self.hblank_clocks = 376usize.saturating_sub(self.fifo.dots());
+
+                            (clocks, Mode::HBlank)
+                        }
+                        None => (0, Mode::Vram),
+                    }
+                } else if clocks >= 172 {
+                    self.draw();
+
+                    self.hblank_clocks = 204;
+
+                    (clocks - 172, Mode::HBlank)
+                } else {
+                    (clocks, Mode::Vram)
+                }
+            }
+            Mode::HBlank => {
+                let hblank_clocks = self.hblank_clocks;
+
+                if clocks >= hblank_clocks {
+                    self.ly += 1;
+
+                    // ly becomes 144 before vblank interrupt
+                    if self.ly > 143 {
+                        self.irq.vblank(true);
+                        self.skip_frame = false;
+
+                        (clocks - hblank_clocks, Mode::VBlank)
+                    } else {
+                        (clocks - hblank_clocks, Mode::Oam)
+                    }
+                } else {
+                    (clocks, Mode::HBlank)
+                }
+            }
+            Mode::VBlank => {
+                if clocks >= 456 {
+                    self.ly += 1;
+
+                    if self.ly > 153 {
+                        self.ly = 0;
+                        self.reset_window();
+
+                        (clocks - 456, Mode::Oam)
+                    } else {
+                        (clocks - 456, Mode::VBlank)
+                    }
+                } else {
+                    (clocks, Mode::VBlank)
+                }
+            }
+            Mode::None => (0, Mode::None),
+        };
+
+        let enter_hblank = self.mode != Mode::HBlank && mode == Mode::HBlank;
+
+        self.clocks = clocks;
+        self.mode = mode;
+
+        self.update_stat();
+
+        self.hdma.run(time, enter_hblank)
+    }
+
+    fn draw(&mut self) {
+        let height = VRAM_HEIGHT;
+        let width = VRAM_WIDTH;
+
+        if self.ly >= height as u8 {
+            return;
+        }
+
+        let mut buf = vec![Color::White.into(); width];
+        // Color index and BG-to-OAM priority of the background/window pixels.
+        let mut bgbuf = vec![(0, false); width];
+
+        // On CGB, LCDC.0 doesn't hide the background; it only clears its priority over sprites.
+        if self.bgenable || self.color {
+            let mapbase = self.bgmap;
+
+            let yy = (self.ly as u16 + self.scy as u16) % 256;
+            let ty = yy / 8;
+            let tyoff = yy % 8;
+
+            for x in 0..width as u16 {
+                let xx = (x + self.scx as u16) % 256;
+                let tx = xx / 8;
+                let txoff = xx % 8;
+
+                let tbase = self.get_tile_base(mapbase, tx, ty);
+                let tattr = self.get_tile_attr(mapbase, tx, ty);
+
+                let tyoff = if tattr.yflip { 7 - tyoff } else { tyoff };
+                let txoff = if tattr.xflip { 7 - txoff } else { txoff };
+
+                let coli = self.get_tile_byte(tbase, txoff, tyoff, tattr.vram_bank);
+                let col = tattr.palette[coli].into();
+
+                buf[x as usize] = col;
+                bgbuf[x as usize] = (coli, tattr.priority);
+            }
+        }
+
+        let winx = self.window_x();
+
+        if let Some(winx) = winx {
+            let mapbase = self.winmap;
+
+            let yy = self.win_line as u16;
+            let ty = yy / 8;
+            let tyoff = yy % 8;
+
+            for x in winx.max(0) as u16..width as u16 {
+                let xx = (x as i16 - winx) as u16;
+                let tx = xx / 8;
+                let txoff = xx % 8;
+
+                let tbase = self.get_tile_base(mapbase, tx, ty);
+                let tattr = self.get_tile_attr(mapbase, tx, ty);
+
+                let tyoff = if tattr.yflip { 7 - tyoff } else { tyoff };
+                let txoff = if tattr.xflip { 7 - txoff } else { txoff };
+
+                let coli = self.get_tile_byte(tbase, txoff, tyoff, tattr.vram_bank);
+                let col = tattr.palette[coli].into();
+
+                buf[x as usize] = col;
+                bgbuf[x as usize] = (coli, tattr.priority);
+            }
+        }
+
+        self.window_line_done(winx.is_some());
+
+        if self.spenable {
+            // Pixels already taken by sprites with higher priority.
+            let mut spbuf = vec![false; width];
+
+            for sp in self.scan_oam() {
+                let attr = self.get_sp_attr(sp.attr);
+                let ly = self.ly as u16;
+                let tyoff = ly + 16 - sp.ypos; // ly - (ypos - 16)
+                let tyoff = if attr.yflip {
+                    self.spsize - 1 - tyoff
+                } else {
+                    tyoff
+                };
+                let ti = if self.spsize == 16 {
+                    if tyoff >= 8 {
+                        sp.ti | 1
+                    } else {
+                        sp.ti & 0xfe
+                    }
+                } else {
+                    sp.ti
+                };
+                let tyoff = tyoff % 8;
+
+                let tiles = 0x8000;
+
+                for x in 0..width as u16 {
+                    if x + 8 < sp.xpos {
+                        continue;
+                    }
+                    let txoff = x + 8 - sp.xpos; // x - (xpos - 8)
+                    if txoff >= 8 {
+                        continue;
+                    }
+                    if spbuf[x as usize] {
+                        // Sprite with higher priority is already there
+                        continue;
+                    }
+                    let txoff = if attr.xflip { 7 - txoff } else { txoff };
+
+                    let tbase = tiles + ti * 16;
+
+                    let coli = self.get_tile_byte(tbase, txoff, tyoff, attr.vram_bank);
+
+                    if coli == 0 {
+                        // Color index 0 means transparent
+                        continue;
+                    }
+
+                    // The opaque pixel hides the sprites with lower priority even if it's behind bg.
+                    spbuf[x as usize] = true;
+
+                    let col = attr.palette[coli];
+
+                    let (bgcoli, bgprio) = bgbuf[x as usize];
+
+                    if self.bg_over_obj(bgcoli, bgprio, attr.priority) {
+                        // If priority is lower than bg color 1-3, don't draw
+                        continue;
+                    }
+
+                    buf[x as usize] = col.into();
+                }
+            }
+        }
+
+        self.vram_update(&buf);
+    }
+
+    /// Send the current line to the frontend unless the frame is skipped.
+    fn vram_update(&self, buf: &[u32]) {
+        if !self.skip_frame {
+            self.hw
+                .get()
+                .borrow_mut()
+                .vram_update(self.ly as usize, buf);
+        }
+    }
+
+    /// Fill the screen with white.
+    pub(crate) fn blank(&self) {
+        let blank = vec![Color::White.into(); VRAM_WIDTH];
+        let mut hw = self.hw.get().borrow_mut();
+        for line in 0..VRAM_HEIGHT {
+            hw.vram_update(line, &blank);
+        }
+    }
+
+    /// Value of LY register visible to the CPU.
+    fn ly_reg(&self) -> u8 {
+        // In line 153, LY becomes 0 after the first 4 dots.
+        if self.ly == 153 && self.mode == Mode::VBlank && self.clocks >= 4 {
+            0
+        } else {
+            self.ly
+        }
+    }
+
+    /// Check if LY matches LYC.
+    fn ly_match(&self) -> bool {
+        self.enable && self.ly_reg() == self.lyc
+    }
+
+    /// Compute the STAT interrupt line from the enabled sources.
+    fn stat_sources(&self, hblank: bool, vblank: bool, oam: bool, lyc: bool) -> bool {
+        let mode = match self.mode {
+            Mode::HBlank => hblank,
+            Mode::VBlank => vblank,
+            Mode::Oam => oam && !self.first_line,
+            Mode::Vram | Mode::None => false,
+        };
+        mode || (lyc && self.ly_match())
+    }
+
+    /// Update the STAT interrupt line, and request the interrupt on its rising edge.
+    /// While the line stays high, the other sources can't trigger another interrupt.
+    fn update_stat(&mut self) {
+        let line = self.stat_sources(
+            self.hblank_interrupt,
+            self.vblank_interrupt,
+            self.oam_interrupt,
+            self.lyc_interrupt,
+        );
+
+        if line && !self.stat_line {
+            self.irq.lcd(true);
+        }
+
+        self.stat_line = line;
+    }
+
+    /// Screen position of the first window pixel in the current line,
+    /// or `None` if the window isn't shown in the line.
+    fn window_x(&self) -> Option<i16> {
+        // On DMG, LCDC.0 hides the window as well as the background.
+        if !self.winenable || !self.wy_latch || (!self.color && !self.bgenable) {
+            return None;
+        }
+
+        if self.win_wrap {
+            return Some(0);
+        }
+
+        match self.wx {
+            // The window is shifted further left by the fine scroll of SCX.
+            0 => Some(-7 - (self.scx % 8) as i16),
+            wx @ 1..=166 => Some(wx as i16 - 7),
+            _ => None,
+        }
+    }
+
+    /// Update the window line counter at the end of the line.
+    fn window_line_done(&mut self, rendered: bool) {
+        // The counter advances only in the lines where the window is actually rendered.
+        if rendered {
+            self.win_line = self.win_line.wrapping_add(1);
+        }
+
+        // WX=166 makes the window span the whole next line.
+        self.win_wrap = rendered && self.wx == 166;
+    }
+
+    /// Reset the window state at the beginning of the frame.
+    fn reset_window(&mut self) {
+        self.wy_latch = false;
+        self.win_line = 0;
+        self.win_wrap = false;
+    }
+
+    /// Check if the background/window pixel is drawn over the sprite pixel.
+    ///
+    /// The background color 1-3 is drawn over the sprite if either the BG map attribute
+    /// (CGB only) or the OAM attribute has the priority bit. On CGB, LCDC.0 clears
+    /// the priority of the background so that sprites are always on top.
+    fn bg_over_obj(&self, bgcoli: usize, bgprio: bool, objprio: bool) -> bool {
+        if self.color && !self.bgenable {
+            return false;
+        }
+        bgcoli != 0 && (bgprio || objprio)
+    }
+
+    /// Write CTRL register (0xff40)
+    pub(crate) fn write_ctrl(&mut self, value: u8) {
+        let old_enable = self.enable;
+
+        self.enable = value & 0x80 != 0;
+        self.winmap = if value & 0x40 != 0 { 0x9c00 } else { 0x9800 };
+        self.winenable = value & 0x20 != 0;
+        self.tiles = if value & 0x10 != 0 { 0x8000 } else { 0x8800 };
+        self.bgmap = if value & 0x08 != 0 { 0x9c00 } else { 0x9800 };
+        self.spsize = if value & 0x04 != 0 { 16 } else { 8 };
+        self.spenable = value & 0x02 != 0;
+        self.bgenable = value & 0x01 != 0;
+
+        if !old_enable && self.enable {
+            info!("LCD enabled");
+            // The first line starts without OAM scan, and is 4 dots shorter than the others.
+            self.clocks = 4;
+            self.ly = 0;
+            self.reset_window();
+            self.mode = Mode::Oam;
+            self.first_line = true;
+            self.skip_frame = true;
+            self.irq.vblank(false);
+            self.hw.get().borrow_mut().lcd_enabled(true);
+        } else if old_enable && !self.enable {
+            info!("LCD disabled");
+            self.clocks = 0;
+            self.ly = 0;
+            self.mode = Mode::None;
+            self.first_line = false;
+            self.irq.vblank(false);
+
+            self.blank();
+            self.hw.get().borrow_mut().lcd_enabled(false);
+        }
+
+        self.update_stat();
+
+        debug!("Write ctrl: {:02x}", value);
+        debug!("Window base: {:04x}", self.winmap);
+        debug!("Window enable: {}", self.winenable);
+        debug!("Bg/window base: {:04x}", self.tiles);
+        debug!("Background base: {:04x}", self.bgmap);
+        debug!("Sprite size: 8x{}", self.spsize);
+        debug!("Sprite enable: {}", self.spenable);
+        debug!("Background enable: {}", self.bgenable);
+    }
+
+    /// Write STAT register (0xff41)
+    pub(crate) fn write_status(&mut self, value: u8) {
+        // On DMG, the write enables all the sources for a moment, which may cause a spurious interrupt.
+        if !self.color && self.stat_sources(true, true, true, true) {
+            if !self.stat_line {
+                debug!("Spurious STAT interrupt");
+                self.irq.lcd(true);
+            }
+            self.stat_line = true;
+        }
+
+        self.lyc_interrupt = value & 0x40 != 0;
+        self.oam_interrupt = value & 0x20 != 0;
+        self.vblank_interrupt = value & 0x10 != 0;
+        self.hblank_interrupt = value & 0x08 != 0;
+
+        debug!("LYC interrupt: {}", self.lyc_interrupt);
+        debug!("OAM interrupt: {}", self.oam_interrupt);
+        debug!("VBlank interrupt: {}", self.vblank_interrupt);
+        debug!("HBlank interrupt: {}", self.hblank_interrupt);
+
+        self.update_stat();
+    }
+
+    // Read CTRL register (0xff40)
+    pub(crate) fn read_ctrl(&self) -> u8 {
+        let mut v = 0;
+        v |= if self.enable { 0x80 } else { 0x00 };
+        v |= if self.winmap == 0x9c00 { 0x40 } else { 0x00 };
+        v |= if self.winenable { 0x20 } else { 0x00 };
+        v |= if self.tiles == 0x8000 { 0x10 } else { 0x00 };
+        v |= if self.bgmap == 0x9c00 { 0x08 } else { 0x00 };
+        v |= if self.spsize == 16 { 0x04 } else { 0x00 };
+        v |= if self.spenable { 0x02 } else { 0x00 };
+        v |= if self.bgenable { 0x01 } else { 0x00 };
+        v
+    }
+
+    // Write STAT register (0xff41)
+    pub(crate) fn read_status(&self) -> u8 {
+        let mut v = 0;
+        v |= if self.lyc_interrupt { 0x40 } else { 0x00 };
+        v |= if self.oam_interrupt { 0x20 } else { 0x00 };
+        v |= if self.vblank_interrupt { 0x10 } else { 0x00 };
+        v |= if self.hblank_interrupt { 0x08 } else { 0x00 };
+        v |= if self.ly_match() { 0x04 } else { 0x00 };
+        v |= if self.first_line {
+            0
+        } else {
+            let p: u8 = self.mode.clone().into();
+            p
+        };
+        trace!("Read Status: {:02x}", v);
+        v
+    }
+
+    /// Check if VRAM is being used by the PPU, which blocks the CPU access.
+    /// CGB palette RAM is blocked in the same period.
+    pub(crate) fn vram_locked(&self) -> bool {
+        self.mode == Mode::Vram
+    }
+
+    /// Check if OAM is being used by the PPU, which blocks the CPU access.
+    pub(crate) fn oam_locked(&self) -> bool {
+        match self.mode {
+            Mode::Oam => !self.first_line,
+            Mode::Vram => true,
+            _ => false,
+        }
+    }
+
+    /// Read OAM region (0xfe00 - 0xfe9f)
+    pub(crate) fn read_oam(&self, addr: u16) -> u8 {
+        self.oam[addr as usize - 0xfe00]
+    }
+
+    /// Write OAM region (0xfe00 - 0xfe9f)
+    pub(crate) fn write_oam(&mut self, addr: u16, v: u8) {
+        self.oam[addr as usize - 0xfe00] = v;
+    }
+
+    /// Read VRAM region (0x8000 - 0x9fff)
+    pub(crate) fn read_vram(&self, addr: u16) -> u8 {
+        self.read_vram_bank(addr, self.vram_select)
+    }
+
+    /// Write VRAM region (0x8000 - 0x9fff)
+    pub(crate) fn write_vram(&mut self, addr: u16, v: u8) {
+        self.write_vram_bank(addr, v, self.vram_select)
+    }
+
+    /// Read SCY register (0xff42)
+    pub(crate) fn read_scy(&self) -> u8 {
+        self.scy
+    }
+
+    /// Write SCY register (0xff42)
+    pub(crate) fn write_scy(&mut self, v: u8) {
+        self.scy = v;
+    }
+
+    /// Read SCX register (0xff43)
+    pub(crate) fn read_scx(&self) -> u8 {
+        self.scx
+    }
+
+    /// Write SCX register (0xff43)
+    pub(crate) fn write_scx(&mut self, v: u8) {
+        self.scx = v;
+    }
+
+    /// Read LY register (0xff44)
+    pub(crate) fn read_ly(&self) -> u8 {
+        self.ly_reg()
+    }
+
+    /// Clear LY register (0xff44)
+    pub(crate) fn clear_ly(&mut self) {
+        self.ly = 0;
+    }
+
+    /// Read LYC register (0xff45)
+    pub(crate) fn read_lyc(&self) -> u8 {
+        self.lyc
+    }
+
+    /// Write LYC register (0xff45)
+    pub(crate) fn write_lyc(&mut self, v: u8) {
+        self.lyc = v;
+        self.update_stat();
+    }
+
+    /// Read BGP register (0xff47)
+    pub(crate) fn read_bg_palette(&self) -> u8 {
+        debug!("Read Bg palette");
+        from_palette(self.bg_palette.clone())
+    }
+
+    /// Write BGP register (0xff47)
+    pub(crate) fn write_bg_palette(&mut self, v: u8) {
+        self.bg_palette = to_palette(v);
+        debug!("Bg palette updated: {:?}", self.bg_palette);
+    }
+
+    /// Read OBP0 register (0xff48)
+    pub(crate) fn read_obj_palette0(&self) -> u8 {
+        debug!("Read Object palette 0");
+        from_palette(self.obj_palette0.clone())
+    }
+
+    /// Write OBP0 register (0xff48)
+    pub(crate) fn write_obj_palette0(&mut self, v: u8) {
+        self.obj_palette0 = to_palette(v);
+        debug!("Object palette 0 updated: {:?}", self.obj_palette0);
+    }
+
+    /// Read OBP1 register (0xff49)
+    pub(crate) fn read_obj_palette1(&self) -> u8 {
+        debug!("Read Object palette 1");
+        from_palette(self.obj_palette1.clone())
+    }
+
+    /// Write OBP1 register (0xff49)
+    pub(crate) fn write_obj_palette1(&mut self, v: u8) {
+        self.obj_palette1 = to_palette(v);
+        debug!("Object palette 1 updated: {:?}", self.obj_palette1);
+    }
+
+    /// Read WY register (0xff4a)
+    pub(crate) fn read_wy(&self) -> u8 {
+        self.wy
+    }
+
+    /// Write WY register (0xff4a)
+    pub(crate) fn write_wy(&mut self, v: u8) {
+        self.wy = v;
+    }
+
+    /// Read WX register (0xff4b)
+    pub(crate) fn read_wx(&self) -> u8 {
+        self.wx
+    }
+
+    /// Write WX register (0xff4b)
+    pub(crate) fn write_wx(&mut self, v: u8) {
+        self.wx = v;
+    }
+
+    /// Read VBK register (0xff4f)
+    pub(crate) fn read_vram_bank_select(&self) -> u8 {
+        self.vram_select as u8 & 0xfe
+    }
+
+    /// Write VBK register (0xff4f)
+    pub(crate) fn select_vram_bank(&mut self, v: u8) {
+        self.vram_select = v as usize & 1;
+    }
+
+    /// Check if HDMA/GDMA is copying a block, which stalls the CPU.
+    pub(crate) fn hdma_active(&self) -> bool {
+        self.hdma.active()
+    }
+
+    /// Read HDMA1 register (0xff51)
+    pub(crate) fn read_hdma_src_high(&self) -> u8 {
+        self.hdma.src_high
+    }
+
+    /// Write HDMA1 register (0xff51)
+    pub(crate) fn write_hdma_src_high(&mut self, v: u8) {
+        self.hdma.src_high = v;
+    }
+
+    /// Read HDMA2 register (0xff52)
+    pub(crate) fn read_hdma_src_low(&self) -> u8 {
+        self.hdma.src_low
+    }
+
+    /// Write HDMA2 register (0xff52)
+    pub(crate) fn write_hdma_src_low(&mut self, v: u8) {
+        self.hdma.src_low = v;
+    }
+
+    /// Read HDMA3 register (0xff53)
+    pub(crate) fn read_hdma_dst_high(&self) -> u8 {
+        self.hdma.dst_high
+    }
+
+    /// Write HDMA3 register (0xff53)
+    pub(crate) fn write_hdma_dst_high(&mut self, v: u8) {
+        self.hdma.dst_high = v;
+    }
+
+    /// Read HDMA4 register (0xff54)
+    pub(crate) fn read_hdma_dst_low(&self) -> u8 {
+        self.hdma.dst_low
+    }
+
+    /// Write HDMA4 register (0xff54)
+    pub(crate) fn write_hdma_dst_low(&mut self, v: u8) {
+        self.hdma.dst_low = v;
+    }
+
+    /// Read HDMA5 register (0xff55)
+    pub(crate) fn read_hdma_start(&self) -> u8 {
+        self.hdma.status()
+    }
+
+    /// Write HDMA5 register (0xff55)
+    pub(crate) fn write_hdma_start(&mut self, v: u8) {
+        self.hdma.start(v);
+
+        if self.hdma.hblank && !self.enable {
+            // While the LCD is off, the first block is copied immediately.
+            self.hdma.trigger();
+        }
+    }
+
+    /// Write BCPS/BGPI register (0xff68)
+    pub(crate) fn select_bg_color_palette(&mut self, v: u8) {
+        self.bg_color_palette.select(v);
+    }
+
+    /// Read BCPS/BGPI register (0xff68)
+    pub(crate) fn read_bg_color_palette_select(&self) -> u8 {
+        self.bg_color_palette.selected()
+    }
+
+    /// Read BCPD/BGPD register (0xff69)
+    pub(crate) fn read_bg_color_palette(&self) -> u8 {
+        self.bg_color_palette.read()
+    }
+
+    /// Write BCPD/BGPD register (0xff69)
+    pub(crate) fn write_bg_color_palette(&mut self, v: u8) {
+        self.bg_color_palette.write(v);
+    }
+
+    /// Write OCPS/OBPI register (0xff6a)
+    pub(crate) fn select_obj_color_palette(&mut self, v: u8) {
+        self.obj_color_palette.select(v);
+    }
+
+    /// Read OCPS/OBPI register (0xff6a)
+    pub(crate) fn read_obj_color_palette_select(&self) -> u8 {
+        self.obj_color_palette.selected()
+    }
+
+    /// Read OCPD/OBPD register (0xff6b)
+    pub(crate) fn read_obj_color_palette(&self) -> u8 {
+        self.obj_color_palette.read()
+    }
+
+    /// Write OCPD/OBPD register (0xff6b)
+    pub(crate) fn write_obj_color_palette(&mut self, v: u8) {
+        self.obj_color_palette.write(v);
+    }
+
+    fn read_vram_bank(&self, addr: u16, bank: usize) -> u8 {
+        let off = addr as usize - 0x8000;
+        self.vram[bank][off]
+    }
+
+    fn write_vram_bank(&mut self, addr: u16, value: u8, bank: usize) {
+        let off = addr as usize - 0x8000;
+        self.vram[bank][off] = value;
+    }
+
+    fn get_tile_base(&self, mapbase: u16, tx: u16, ty: u16) -> u16 {
+        let ti = tx + ty * 32;
+        let num = self.read_vram_bank(mapbase + ti, 0);
+
+        if self.tiles == 0x8000 {
+            self.tiles + num as u16 * 16
+        } else {
+            self.tiles + (0x800 + num as i8 as i16 * 16) as u16
+        }
+    }
+
+    fn get_tile_attr(&self, mapbase: u16, tx: u16, ty: u16) -> MapAttribute {
+        if self.color {
+            let ti = tx + ty * 32;
+            let attr = self.read_vram_bank(mapbase + ti, 1) as usize;
+
+            MapAttribute {
+                palette: &self.bg_color_palette.cols[attr & 0x7][..],
+                vram_bank: (attr >> 3) & 1,
+                xflip: attr & 0x20 != 0,
+                yflip: attr & 0x40 != 0,
+                priority: attr & 0x80 != 0,
+            }
+        } else {
+            MapAttribute {
+                palette: &self.bg_palette,
+                vram_bank: 0,
+                xflip: false,
+                yflip: false,
+                priority: false,
+            }
+        }
+    }
+
+    fn get_sp_attr(&self, attr: u8) -> MapAttribute {
+        if self.color {
+            let attr = attr as usize;
+
+            MapAttribute {
+                palette: &self.obj_color_palette.cols[attr & 0x7][..],
+                vram_bank: (attr >> 3) & 1,
+                xflip: attr & 0x20 != 0,
+                yflip: attr & 0x40 != 0,
+                priority: attr & 0x80 != 0,
+            }
+        } else {
+            let palette = if attr & 0x10 != 0 {
+                &self.obj_palette1
+            } else {
+                &self.obj_palette0
+            };
+
+            MapAttribute {
+                palette,
+                vram_bank: 0,
+                xflip: attr & 0x20 != 0,
+                yflip: attr & 0x40 != 0,
+                priority: attr & 0x80 != 0,
+            }
+        }
+    }
+
+    /// Select the sprites drawn on the current line, sorted by their drawing priority.
+    ///
+    /// Like the hardware OAM scan, only the first 10 sprites in OAM hitting the line are selected
+    /// unless the sprite limit is disabled. On DMG, the sprite with the smaller x coordinate
+    /// has the higher priority, and the OAM index breaks ties. On CGB, only the OAM index matters.
+    fn scan_oam(&self) -> Vec<Sprite> {
+        let ly = self.ly as u16;
+        let limit = if self.sprite_limit { 10 } else { 40 };
+
+        let mut sprites: Vec<_> = self
+            .oam
+            .chunks(4)
+            .enumerate()
+            .filter(|(_, oam)| {
+                let ypos = oam[0] as u16;
+                ly + 16 >= ypos && ly + 16 - ypos < self.spsize
+            })
+            .take(limit)
+            .map(|(index, oam)| Sprite {
+                index,
+                ypos: oam[0] as u16,
+                xpos: oam[1] as u16,
+                ti: oam[2] as u16,
+                attr: oam[3],
+            })
+            .collect();
+
+        if !self.color {
+            // Stable sort keeps OAM order for the sprites with the same x coordinate.
+            sprites.sort_by_key(|sp| sp.xpos);
+        }
+
+        sprites
+    }
+
+    fn get_tile_byte(&self, tilebase: u16, txoff: u16, tyoff: u16, bank: usize) -> usize {
+        let l = self.read_vram_bank(tilebase + tyoff * 2, bank);
+        let h = self.read_vram_bank(tilebase + tyoff * 2 + 1, bank);
+
+        let l = (l >> (7 - txoff)) & 1;
+        let h = ((h >> (7 - txoff)) & 1) << 1;
+
+        (h | l) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::TestHardware;
+    use crate::ic::Ic;
+
+    fn gpu(color: bool, sprite_limit: bool) -> Gpu {
+        Gpu::new(
+            HardwareHandle::new(TestHardware::default()),
+            Irq::new(),
+            color,
+            sprite_limit,
+            false,
+        )
+    }
+
+    fn set_sprite(gpu: &mut Gpu, index: usize, ypos: u8, xpos: u8) {
+        let base = 0xfe00 + index as u16 * 4;
+        gpu.write_oam(base, ypos);
+        gpu.write_oam(base + 1, xpos);
+        gpu.write_oam(base + 2, index as u8);
+    }
+
+    fn scanned(gpu: &Gpu) -> Vec<(u16, u16)> {
+        gpu.scan_oam().iter().map(|sp| (sp.ti, sp.xpos)).collect()
+    }
+
+    #[test]
+    fn test_sprite_limit() {
+        let mut g = gpu(false, true);
+
+        // 12 sprites on line 0, and 1 sprite out of the line.
+        set_sprite(&mut g, 0, 40, 8);
+        for i in 1..13 {
+            set_sprite(&mut g, i, 16, i as u8 * 8);
+        }
+
+        let s = scanned(&g);
+        assert_eq!(s.len(), 10);
+        assert_eq!(s.first(), Some(&(1, 8)));
+        assert_eq!(s.last(), Some(&(10, 80)));
+
+        let mut g = gpu(false, false);
+        for i in 0..13 {
+            set_sprite(&mut g, i, 16, i as u8 * 8);
+        }
+        assert_eq!(scanned(&g).len(), 13);
+    }
+
+    #[test]
+    fn test_sprite_priority() {
+        let mut g = gpu(false, true);
+
+        set_sprite(&mut g, 0, 16, 30);
+        set_sprite(&mut g, 1, 16, 10);
+        set_sprite(&mut g, 2, 16, 30);
+        set_sprite(&mut g, 3, 16, 20);
+
+        // DMG: smaller x first, then smaller OAM index.
+        assert_eq!(scanned(&g), vec![(1, 10), (3, 20), (0, 30), (2, 30)]);
+
+        let mut g = gpu(true, true);
+
+        set_sprite(&mut g, 0, 16, 30);
+        set_sprite(&mut g, 1, 16, 10);
+        set_sprite(&mut g, 2, 16, 30);
+        set_sprite(&mut g, 3, 16, 20);
+
+        // CGB: OAM index only.
+        assert_eq!(scanned(&g), vec![(0, 30), (1, 10), (2, 30), (3, 20)]);
+    }
+
+    fn mode3_dots(g: &mut Gpu) -> usize {
+        g.fifo_start();
+        g.fifo_run(1000).map(|rest| 1000 - rest).unwrap()
+    }
+
+    #[test]
+    fn test_fifo_mode3_length() {
+        let mut g = gpu(false, true);
+        g.write_ctrl(0x91);
+
+        // No scroll, no sprites.
+        assert_eq!(mode3_dots(&mut g), 172);
+
+        // Discarding pixels scrolled out by SCX.
+        g.write_scx(3);
+        assert_eq!(mode3_dots(&mut g), 175);
+        g.write_scx(8);
+        assert_eq!(mode3_dots(&mut g), 172);
+
+        // Fetching sprites extends mode 3.
+        g.write_scx(0);
+        g.write_ctrl(0x93);
+        set_sprite(&mut g, 0, 16, 16);
+        assert!(mode3_dots(&mut g) > 172);
+    }
+
+    fn run_lines(g: &mut Gpu, lines: usize) {
+        for _ in 0..lines * 114 {
+            g.step(4);
+        }
+    }
+
+    fn start_frame(g: &mut Gpu) {
+        while !(g.ly == 0 && g.mode == Mode::Oam) {
+            g.step(4);
+        }
+    }
+
+    #[test]
+    fn test_window_line_counter() {
+        for pixel_fifo in [false, true] {
+            let mut g = gpu(false, true);
+            g.pixel_fifo = pixel_fifo;
+            g.write_wy(0);
+            g.write_wx(7);
+            g.write_ctrl(0xb1);
+            start_frame(&mut g);
+
+            run_lines(&mut g, 2);
+            assert_eq!(g.win_line, 2);
+
+            // The counter stops while the window is disabled.
+            g.write_ctrl(0x91);
+            run_lines(&mut g, 3);
+            assert_eq!(g.win_line, 2);
+
+            // Moving WY after the latch doesn't hide the window.
+            g.write_ctrl(0xb1);
+            g.write_wy(100);
+            run_lines(&mut g, 1);
+            assert_eq!(g.win_line, 3);
+
+            // The window is hidden for WX > 166.
+            g.write_wx(167);
+            run_lines(&mut g, 1);
+            assert_eq!(g.win_line, 3);
+
+            // The window isn't shown until LY matches WY in the next frame.
+            g.write_wx(7);
+            start_frame(&mut g);
+            run_lines(&mut g, 100);
+            assert_eq!(g.win_line, 0);
+            run_lines(&mut g, 2);
+            assert_eq!(g.win_line, 2);
+        }
+    }
+
+    #[test]
+    fn test_bg_over_obj() {
+        let mut g = gpu(false, true);
+        g.write_ctrl(0x93);
+
+        // DMG: only the OAM priority bit matters.
+        assert!(!g.bg_over_obj(0, false, true));
+        assert!(g.bg_over_obj(1, false, true));
+        assert!(!g.bg_over_obj(1, false, false));
+
+        let mut g = gpu(true, true);
+        g.write_ctrl(0x93);
+
+        // CGB: either BG attribute or OAM attribute gives the priority to color 1-3.
+        assert!(g.bg_over_obj(1, true, false));
+        assert!(g.bg_over_obj(1, false, true));
+        assert!(!g.bg_over_obj(0, true, true));
+        assert!(!g.bg_over_obj(1, false, false));
+
+        // CGB: LCDC.0 clears the priority.
+        g.write_ctrl(0x92);
+        assert!(!g.bg_over_obj(1, true, true));
+    }
+
+    fn take_lcd_irq(ic: &mut Ic) -> bool {
+        let v = ic.read_flags() & 0x02 != 0;
+        ic.write_flags(0);
+        v
+    }
+
+    fn run_until(g: &mut Gpu, ly: u8, mode: Mode) {
+        while !(g.ly == ly && g.mode == mode) {
+            g.step(4);
+        }
+    }
+
+    #[test]
+    fn test_stat_blocking() {
+        let mut g = gpu(true, true);
+        let mut ic = Ic::new(g.irq.clone());
+        g.write_ctrl(0x91);
+        start_frame(&mut g);
+
+        // HBlank and OAM sources are high back to back, so only HBlank fires.
+        g.write_status(0x28);
+        take_lcd_irq(&mut ic);
+        run_until(&mut g, 0, Mode::HBlank);
+        assert!(take_lcd_irq(&mut ic));
+        run_until(&mut g, 1, Mode::Oam);
+        assert!(!take_lcd_irq(&mut ic));
+
+        // LYC interrupt fires only once while LY matches LYC.
+        g.write_status(0x40);
+        g.write_lyc(3);
+        run_until(&mut g, 3, Mode::Oam);
+        assert!(take_lcd_irq(&mut ic));
+        run_until(&mut g, 3, Mode::HBlank);
+        assert!(!take_lcd_irq(&mut ic));
+
+        // Enabling a source while the condition holds causes a rising edge.
+        g.write_status(0x00);
+        g.write_status(0x40);
+        assert!(take_lcd_irq(&mut ic));
+    }
+
+    #[test]
+    fn test_stat_line_153() {
+        let mut g = gpu(true, true);
+        let mut ic = Ic::new(g.irq.clone());
+        g.write_ctrl(0x91);
+        g.write_lyc(0);
+        g.write_status(0x40);
+        run_until(&mut g, 152, Mode::VBlank);
+        take_lcd_irq(&mut ic);
+
+        // LY reads 0 and matches LYC after the first 4 dots of line 153.
+        run_until(&mut g, 153, Mode::VBlank);
+        g.step(4);
+        assert_eq!(g.read_ly(), 0);
+        assert!(g.read_status() & 0x04 != 0);
+        assert!(take_lcd_irq(&mut ic));
+
+        // The line stays high into line 0, so no extra interrupt.
+        run_until(&mut g, 0, Mode::Oam);
+        assert!(!take_lcd_irq(&mut ic));
+    }
+
+    #[test]
+    fn test_stat_write_bug() {
+        for color in [false, true] {
+            let mut g = gpu(color, true);
+            let mut ic = Ic::new(g.irq.clone());
+            g.write_ctrl(0x91);
+            run_until(&mut g, 10, Mode::HBlank);
+            take_lcd_irq(&mut ic);
+
+            // Writing STAT in HBlank causes a spurious interrupt only on DMG.
+            g.write_status(0x00);
+            assert_eq!(take_lcd_irq(&mut ic), !color);
+        }
+    }
+
+    #[test]
+    fn test_lcd_on_off() {
+        let hw = TestHardware::default();
+        let mut g = Gpu::new(
+            HardwareHandle::new(hw.clone()),
+            Irq::new(),
+            false,
+            true,
+            false,
+        );
+
+        // The first line doesn't have mode 2.
+        g.write_ctrl(0x91);
+        assert_eq!(g.read_ly(), 0);
+        assert_eq!(g.read_status() & 0x3, 0);
+        run_until(&mut g, 0, Mode::Vram);
+        assert_eq!(g.read_status() & 0x3, 3);
+
+        // The first frame isn't displayed.
+        run_until(&mut g, 144, Mode::VBlank);
+        assert!(hw.lines.borrow().is_empty());
+        run_until(&mut g, 1, Mode::Oam);
+        assert_eq!(*hw.lines.borrow(), vec![0]);
+
+        // Turning off the LCD resets LY and mode, and blanks the screen.
+        run_until(&mut g, 50, Mode::HBlank);
+        hw.lines.borrow_mut().clear();
+        g.write_ctrl(0x11);
+        assert_eq!(g.read_ly(), 0);
+        assert_eq!(g.read_status() & 0x3, 0);
+        assert_eq!(*hw.lines.borrow(), (0..VRAM_HEIGHT).collect::<Vec<_>>());
+
+        for _ in 0..1000 {
+            g.step(4);
+        }
+        assert_eq!(g.read_ly(), 0);
+        assert_eq!(*hw.lcd.borrow(), vec![true, false]);
+    }
+
+    #[test]
+    fn test_access_lock() {
+        let mut g = gpu(false, true);
+
+        // Not locked while the LCD is off.
+        assert!(!g.vram_locked());
+        assert!(!g.oam_locked());
+
+        // OAM isn't scanned in the first line after the LCD is turned on.
+        g.write_ctrl(0x91);
+        assert!(!g.oam_locked());
+
+        run_until(&mut g, 0, Mode::Vram);
+        assert!(g.vram_locked());
+        assert!(g.oam_locked());
+
+        run_until(&mut g, 0, Mode::HBlank);
+        assert!(!g.vram_locked());
+        assert!(!g.oam_locked());
+
+        run_until(&mut g, 1, Mode::Oam);
+        assert!(!g.vram_locked());
+        assert!(g.oam_locked());
+    }
+
+    fn hdma(g: &mut Gpu, src: u16, dst: u16, v: u8) {
+        g.write_hdma_src_high((src >> 8) as u8);
+        g.write_hdma_src_low(src as u8);
+        g.write_hdma_dst_high((dst >> 8) as u8);
+        g.write_hdma_dst_low(dst as u8);
+        g.write_hdma_start(v);
+    }
+
+    /// Run the GPU until HDMA stops copying, and return the copied ranges and the dots taken.
+    fn run_hdma(g: &mut Gpu) -> (Vec<(u16, u16, u16)>, usize) {
+        let mut reqs = Vec::new();
+        let mut dots = 0;
+        while g.hdma_active() {
+            if let Some(req) = g.step(4) {
+                reqs.push((req.src(), req.dst(), req.len()));
+            }
+            dots += 4;
+        }
+        (reqs, dots)
+    }
+
+    #[test]
+    fn test_gdma() {
+        let mut g = gpu(true, true);
+        assert_eq!(g.read_hdma_start(), 0xff);
+
+        // 2 blocks take 16 machine cycles.
+        hdma(&mut g, 0xc012, 0x9ff5, 0x01);
+        assert_eq!(g.read_hdma_start(), 0x01);
+        let (reqs, dots) = run_hdma(&mut g);
+        assert_eq!(dots, 64);
+        assert_eq!(reqs.len(), 16);
+        assert_eq!(reqs[0], (0xc010, 0x9ff0, 2));
+
+        // The destination wraps around in VRAM.
+        assert_eq!(reqs[8], (0xc020, 0x8000, 2));
+        assert_eq!(g.read_hdma_start(), 0xff);
+    }
+
+    #[test]
+    fn test_hdma() {
+        let mut g = gpu(true, true);
+        g.write_ctrl(0x91);
+
+        hdma(&mut g, 0x4000, 0x8000, 0x82);
+        assert_eq!(g.read_hdma_start(), 0x02);
+        assert!(!g.hdma_active());
+
+        // One block per hblank.
+        run_until(&mut g, 0, Mode::Vram);
+        let mut reqs = Vec::new();
+        while g.mode == Mode::Vram || g.hdma_active() {
+            if let Some(req) = g.step(4) {
+                reqs.push((req.src(), req.dst(), req.len()));
+            }
+        }
+        assert_eq!(reqs.len(), 8);
+        assert_eq!(reqs.first(), Some(&(0x4000, 0x8000, 2)));
+        assert_eq!(g.read_hdma_start(), 0x01);
+
+        run_until(&mut g, 1, Mode::HBlank);
+        run_hdma(&mut g);
+        assert_eq!(g.read_hdma_start(), 0x00);
+
+        // Cancel the transfer.
+        g.write_hdma_start(0x00);
+        assert_eq!(g.read_hdma_start(), 0x80);
+        run_until(&mut g, 2, Mode::HBlank);
+        assert!(!g.hdma_active());
+    }
+
+    #[test]
+    fn test_hdma_lcd_off() {
+        let mut g = gpu(true, true);
+
+        // The first block is copied immediately while the LCD is off.
+        hdma(&mut g, 0x4000, 0x8000, 0x81);
+        assert!(g.hdma_active());
+        run_hdma(&mut g);
+        assert_eq!(g.read_hdma_start(), 0x00);
+    }
+}
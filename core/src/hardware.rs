@@ -67,6 +67,11 @@ pub trait Hardware {
     /// Called when one horizontal line in the display is updated.
     fn vram_update(&mut self, line: usize, buffer: &[u32]);
 
+    /// Called when the LCD is turned on or off.
+    /// While the LCD is off, the display is blank and no line is updated.
+    /// The first frame after the LCD is turned on isn't displayed either.
+    fn lcd_enabled(&mut self, _enabled: bool) {}
+
     /// Called when the emulator checks if the key is pressed.
     fn joypad_pressed(&mut self, key: Key) -> bool;
 
@@ -96,3 +101,47 @@ pub trait Hardware {
     /// Called when the CPU attempts to read save data from the cartridge battery-backed RAM.
     fn save_ram(&mut self, ram: &[u8]);
 }
+
+/// Hardware for unit tests, which records the updated lines, the LCD on/off and the saved RAM.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct TestHardware {
+    pub(crate) lines: Rc<RefCell<Vec<usize>>>,
+    pub(crate) lcd: Rc<RefCell<Vec<bool>>>,
+    pub(crate) saved: Rc<RefCell<Vec<Vec<u8>>>>,
+}
+
+#[cfg(test)]
+impl Hardware for TestHardware {
+    fn vram_update(&mut self, line: usize, _: &[u32]) {
+        self.lines.borrow_mut().push(line);
+    }
+
+    fn lcd_enabled(&mut self, enabled: bool) {
+        self.lcd.borrow_mut().push(enabled);
+    }
+
+    fn joypad_pressed(&mut self, _: Key) -> bool {
+        false
+    }
+
+    fn sound_play(&mut self, _: Box<dyn Stream>) {}
+
+    fn clock(&mut self) -> u64 {
+        0
+    }
+
+    fn send_byte(&mut self, _: u8) {}
+
+    fn recv_byte(&mut self) -> Option<u8> {
+        None
+    }
+
+    fn load_ram(&mut self, size: usize) -> Vec<u8> {
+        alloc::vec![0; size]
+    }
+
+    fn save_ram(&mut self, ram: &[u8]) {
+        self.saved.borrow_mut().push(ram.to_vec());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::alu;
 use crate::cpu::{Cpu, Sys};
+use crate::disasm::{Attrs, FlagEffect};
 use hashbrown::HashMap;
 use lazy_static::lazy_static;
 use log::*;
@@ -2909,8 +2910,9 @@ impl<T: Sys> Cpu<T> {
         let flg = !self.get_zf();
         let pc = self.fetch16();
         if flg {
+            self.step(4);
             self.push(self.get_pc());
-            self.jump(pc);
+            self.set_pc(pc);
             return 24;
         }
 
@@ -2920,8 +2922,8 @@ impl<T: Sys> Cpu<T> {
     /// push bc
     #[allow(unused_variables)]
     fn op_00c5(&mut self) -> usize {
-        self.push(self.get_bc());
         self.step(4);
+        self.push(self.get_bc());
 
         16
     }
@@ -2945,8 +2947,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00c7(&mut self) -> usize {
         let pc = 0x00u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -2999,8 +3002,9 @@ impl<T: Sys> Cpu<T> {
         let flg = self.get_zf();
         let pc = self.fetch16();
         if flg {
+            self.step(4);
             self.push(self.get_pc());
-            self.jump(pc);
+            self.set_pc(pc);
             return 24;
         }
 
@@ -3011,8 +3015,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00cd(&mut self) -> usize {
         let pc = self.fetch16();
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         24
     }
@@ -3036,8 +3041,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00cf(&mut self) -> usize {
         let pc = 0x08u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -3084,8 +3090,9 @@ impl<T: Sys> Cpu<T> {
         let flg = !self.get_cf();
         let pc = self.fetch16();
         if flg {
+            self.step(4);
             self.push(self.get_pc());
-            self.jump(pc);
+            self.set_pc(pc);
             return 24;
         }
 
@@ -3095,8 +3102,8 @@ impl<T: Sys> Cpu<T> {
     /// push de
     #[allow(unused_variables)]
     fn op_00d5(&mut self) -> usize {
-        self.push(self.get_de());
         self.step(4);
+        self.push(self.get_de());
 
         16
     }
@@ -3120,8 +3127,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00d7(&mut self) -> usize {
         let pc = 0x10u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -3169,8 +3177,9 @@ impl<T: Sys> Cpu<T> {
         let flg = self.get_cf();
         let pc = self.fetch16();
         if flg {
+            self.step(4);
             self.push(self.get_pc());
-            self.jump(pc);
+            self.set_pc(pc);
             return 24;
         }
 
@@ -3196,8 +3205,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00df(&mut self) -> usize {
         let pc = 0x18u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -3234,8 +3244,8 @@ impl<T: Sys> Cpu<T> {
     /// push hl
     #[allow(unused_variables)]
     fn op_00e5(&mut self) -> usize {
-        self.push(self.get_hl());
         self.step(4);
+        self.push(self.get_hl());
 
         16
     }
@@ -3258,8 +3268,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00e7(&mut self) -> usize {
         let pc = 0x20u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -3318,8 +3329,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00ef(&mut self) -> usize {
         let pc = 0x28u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -3368,8 +3380,8 @@ impl<T: Sys> Cpu<T> {
     /// push af
     #[allow(unused_variables)]
     fn op_00f5(&mut self) -> usize {
-        self.push(self.get_af());
         self.step(4);
+        self.push(self.get_af());
 
         16
     }
@@ -3392,8 +3404,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00f7(&mut self) -> usize {
         let pc = 0x30u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -3463,8 +3476,9 @@ impl<T: Sys> Cpu<T> {
     #[allow(unused_variables)]
     fn op_00ff(&mut self) -> usize {
         let pc = 0x38u16;
+        self.step(4);
         self.push(self.get_pc());
-        self.jump(pc);
+        self.set_pc(pc);
 
         16
     }
@@ -6755,13 +6769,5032 @@ pub fn mnem(code: u16) -> &'static str {
     MNEMONICS.get(&code).unwrap_or(&"(unknown opcode)")
 }
 
+/// Return the static attributes of the instruction for the given opcode.
+pub fn attrs(code: u16) -> Option<Attrs> {
+    let attrs = match code {
+        0x0000 => Attrs {
+            operator: "nop",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0001 => Attrs {
+            operator: "ld",
+            operands: &["bc", "d16"],
+            size: 3,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0002 => Attrs {
+            operator: "ld",
+            operands: &["(bc)", "a"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0003 => Attrs {
+            operator: "inc",
+            operands: &["bc"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0004 => Attrs {
+            operator: "inc",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0005 => Attrs {
+            operator: "dec",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0006 => Attrs {
+            operator: "ld",
+            operands: &["b", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0007 => Attrs {
+            operator: "rlca",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Reset,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0x0008 => Attrs {
+            operator: "ld",
+            operands: &["(a16)", "sp"],
+            size: 3,
+            time: (20, 20),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0009 => Attrs {
+            operator: "add",
+            operands: &["hl", "bc"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x000a => Attrs {
+            operator: "ld",
+            operands: &["a", "(bc)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x000b => Attrs {
+            operator: "dec",
+            operands: &["bc"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x000c => Attrs {
+            operator: "inc",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x000d => Attrs {
+            operator: "dec",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x000e => Attrs {
+            operator: "ld",
+            operands: &["c", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x000f => Attrs {
+            operator: "rrca",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Reset,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0x0010 => Attrs {
+            operator: "stop",
+            operands: &["0"],
+            size: 2,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0011 => Attrs {
+            operator: "ld",
+            operands: &["de", "d16"],
+            size: 3,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0012 => Attrs {
+            operator: "ld",
+            operands: &["(de)", "a"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0013 => Attrs {
+            operator: "inc",
+            operands: &["de"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0014 => Attrs {
+            operator: "inc",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0015 => Attrs {
+            operator: "dec",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0016 => Attrs {
+            operator: "ld",
+            operands: &["d", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0017 => Attrs {
+            operator: "rla",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Reset,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0x0018 => Attrs {
+            operator: "jr",
+            operands: &["r8"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0019 => Attrs {
+            operator: "add",
+            operands: &["hl", "de"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x001a => Attrs {
+            operator: "ld",
+            operands: &["a", "(de)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x001b => Attrs {
+            operator: "dec",
+            operands: &["de"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x001c => Attrs {
+            operator: "inc",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x001d => Attrs {
+            operator: "dec",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x001e => Attrs {
+            operator: "ld",
+            operands: &["e", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x001f => Attrs {
+            operator: "rra",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Reset,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0x0020 => Attrs {
+            operator: "jr",
+            operands: &["nz", "r8"],
+            size: 2,
+            time: (12, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0021 => Attrs {
+            operator: "ld",
+            operands: &["hl", "d16"],
+            size: 3,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0022 => Attrs {
+            operator: "ldi",
+            operands: &["(hl)", "a"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0023 => Attrs {
+            operator: "inc",
+            operands: &["hl"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0024 => Attrs {
+            operator: "inc",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0025 => Attrs {
+            operator: "dec",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0026 => Attrs {
+            operator: "ld",
+            operands: &["h", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0027 => Attrs {
+            operator: "daa",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0x0028 => Attrs {
+            operator: "jr",
+            operands: &["z", "r8"],
+            size: 2,
+            time: (12, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0029 => Attrs {
+            operator: "add",
+            operands: &["hl", "hl"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x002a => Attrs {
+            operator: "ldi",
+            operands: &["a", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x002b => Attrs {
+            operator: "dec",
+            operands: &["hl"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x002c => Attrs {
+            operator: "inc",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x002d => Attrs {
+            operator: "dec",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x002e => Attrs {
+            operator: "ld",
+            operands: &["l", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x002f => Attrs {
+            operator: "cpl",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0x0030 => Attrs {
+            operator: "jr",
+            operands: &["nc", "r8"],
+            size: 2,
+            time: (12, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0031 => Attrs {
+            operator: "ld",
+            operands: &["sp", "d16"],
+            size: 3,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0032 => Attrs {
+            operator: "ldd",
+            operands: &["(hl)", "a"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0033 => Attrs {
+            operator: "inc",
+            operands: &["sp"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0034 => Attrs {
+            operator: "inc",
+            operands: &["(hl)"],
+            size: 1,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0035 => Attrs {
+            operator: "dec",
+            operands: &["(hl)"],
+            size: 1,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0036 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "d8"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0037 => Attrs {
+            operator: "scf",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Set,
+        },
+        0x0038 => Attrs {
+            operator: "jr",
+            operands: &["cf", "r8"],
+            size: 2,
+            time: (12, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0039 => Attrs {
+            operator: "add",
+            operands: &["hl", "sp"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x003a => Attrs {
+            operator: "ldd",
+            operands: &["a", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x003b => Attrs {
+            operator: "dec",
+            operands: &["sp"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x003c => Attrs {
+            operator: "inc",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x003d => Attrs {
+            operator: "dec",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Unaffected,
+        },
+        0x003e => Attrs {
+            operator: "ld",
+            operands: &["a", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x003f => Attrs {
+            operator: "ccf",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0x0040 => Attrs {
+            operator: "ld",
+            operands: &["b", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0041 => Attrs {
+            operator: "ld",
+            operands: &["b", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0042 => Attrs {
+            operator: "ld",
+            operands: &["b", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0043 => Attrs {
+            operator: "ld",
+            operands: &["b", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0044 => Attrs {
+            operator: "ld",
+            operands: &["b", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0045 => Attrs {
+            operator: "ld",
+            operands: &["b", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0046 => Attrs {
+            operator: "ld",
+            operands: &["b", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0047 => Attrs {
+            operator: "ld",
+            operands: &["b", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0048 => Attrs {
+            operator: "ld",
+            operands: &["c", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0049 => Attrs {
+            operator: "ld",
+            operands: &["c", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x004a => Attrs {
+            operator: "ld",
+            operands: &["c", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x004b => Attrs {
+            operator: "ld",
+            operands: &["c", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x004c => Attrs {
+            operator: "ld",
+            operands: &["c", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x004d => Attrs {
+            operator: "ld",
+            operands: &["c", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x004e => Attrs {
+            operator: "ld",
+            operands: &["c", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x004f => Attrs {
+            operator: "ld",
+            operands: &["c", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0050 => Attrs {
+            operator: "ld",
+            operands: &["d", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0051 => Attrs {
+            operator: "ld",
+            operands: &["d", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0052 => Attrs {
+            operator: "ld",
+            operands: &["d", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0053 => Attrs {
+            operator: "ld",
+            operands: &["d", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0054 => Attrs {
+            operator: "ld",
+            operands: &["d", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0055 => Attrs {
+            operator: "ld",
+            operands: &["d", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0056 => Attrs {
+            operator: "ld",
+            operands: &["d", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0057 => Attrs {
+            operator: "ld",
+            operands: &["d", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0058 => Attrs {
+            operator: "ld",
+            operands: &["e", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0059 => Attrs {
+            operator: "ld",
+            operands: &["e", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x005a => Attrs {
+            operator: "ld",
+            operands: &["e", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x005b => Attrs {
+            operator: "ld",
+            operands: &["e", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x005c => Attrs {
+            operator: "ld",
+            operands: &["e", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x005d => Attrs {
+            operator: "ld",
+            operands: &["e", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x005e => Attrs {
+            operator: "ld",
+            operands: &["e", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x005f => Attrs {
+            operator: "ld",
+            operands: &["e", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0060 => Attrs {
+            operator: "ld",
+            operands: &["h", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0061 => Attrs {
+            operator: "ld",
+            operands: &["h", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0062 => Attrs {
+            operator: "ld",
+            operands: &["h", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0063 => Attrs {
+            operator: "ld",
+            operands: &["h", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0064 => Attrs {
+            operator: "ld",
+            operands: &["h", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0065 => Attrs {
+            operator: "ld",
+            operands: &["h", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0066 => Attrs {
+            operator: "ld",
+            operands: &["h", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0067 => Attrs {
+            operator: "ld",
+            operands: &["h", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0068 => Attrs {
+            operator: "ld",
+            operands: &["l", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0069 => Attrs {
+            operator: "ld",
+            operands: &["l", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x006a => Attrs {
+            operator: "ld",
+            operands: &["l", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x006b => Attrs {
+            operator: "ld",
+            operands: &["l", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x006c => Attrs {
+            operator: "ld",
+            operands: &["l", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x006d => Attrs {
+            operator: "ld",
+            operands: &["l", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x006e => Attrs {
+            operator: "ld",
+            operands: &["l", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x006f => Attrs {
+            operator: "ld",
+            operands: &["l", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0070 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "b"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0071 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "c"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0072 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "d"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0073 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "e"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0074 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "h"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0075 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "l"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0076 => Attrs {
+            operator: "halt",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0077 => Attrs {
+            operator: "ld",
+            operands: &["(hl)", "a"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0078 => Attrs {
+            operator: "ld",
+            operands: &["a", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0079 => Attrs {
+            operator: "ld",
+            operands: &["a", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x007a => Attrs {
+            operator: "ld",
+            operands: &["a", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x007b => Attrs {
+            operator: "ld",
+            operands: &["a", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x007c => Attrs {
+            operator: "ld",
+            operands: &["a", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x007d => Attrs {
+            operator: "ld",
+            operands: &["a", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x007e => Attrs {
+            operator: "ld",
+            operands: &["a", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x007f => Attrs {
+            operator: "ld",
+            operands: &["a", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x0080 => Attrs {
+            operator: "add",
+            operands: &["a", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0081 => Attrs {
+            operator: "add",
+            operands: &["a", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0082 => Attrs {
+            operator: "add",
+            operands: &["a", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0083 => Attrs {
+            operator: "add",
+            operands: &["a", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0084 => Attrs {
+            operator: "add",
+            operands: &["a", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0085 => Attrs {
+            operator: "add",
+            operands: &["a", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0086 => Attrs {
+            operator: "add",
+            operands: &["a", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0087 => Attrs {
+            operator: "add",
+            operands: &["a", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0088 => Attrs {
+            operator: "adc",
+            operands: &["a", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0089 => Attrs {
+            operator: "adc",
+            operands: &["a", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x008a => Attrs {
+            operator: "adc",
+            operands: &["a", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x008b => Attrs {
+            operator: "adc",
+            operands: &["a", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x008c => Attrs {
+            operator: "adc",
+            operands: &["a", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x008d => Attrs {
+            operator: "adc",
+            operands: &["a", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x008e => Attrs {
+            operator: "adc",
+            operands: &["a", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x008f => Attrs {
+            operator: "adc",
+            operands: &["a", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0090 => Attrs {
+            operator: "sub",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0091 => Attrs {
+            operator: "sub",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0092 => Attrs {
+            operator: "sub",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0093 => Attrs {
+            operator: "sub",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0094 => Attrs {
+            operator: "sub",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0095 => Attrs {
+            operator: "sub",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0096 => Attrs {
+            operator: "sub",
+            operands: &["(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0097 => Attrs {
+            operator: "sub",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0098 => Attrs {
+            operator: "sbc",
+            operands: &["a", "b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x0099 => Attrs {
+            operator: "sbc",
+            operands: &["a", "c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x009a => Attrs {
+            operator: "sbc",
+            operands: &["a", "d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x009b => Attrs {
+            operator: "sbc",
+            operands: &["a", "e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x009c => Attrs {
+            operator: "sbc",
+            operands: &["a", "h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x009d => Attrs {
+            operator: "sbc",
+            operands: &["a", "l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x009e => Attrs {
+            operator: "sbc",
+            operands: &["a", "(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x009f => Attrs {
+            operator: "sbc",
+            operands: &["a", "a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00a0 => Attrs {
+            operator: "and",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a1 => Attrs {
+            operator: "and",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a2 => Attrs {
+            operator: "and",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a3 => Attrs {
+            operator: "and",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a4 => Attrs {
+            operator: "and",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a5 => Attrs {
+            operator: "and",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a6 => Attrs {
+            operator: "and",
+            operands: &["(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a7 => Attrs {
+            operator: "and",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00a8 => Attrs {
+            operator: "xor",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00a9 => Attrs {
+            operator: "xor",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00aa => Attrs {
+            operator: "xor",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00ab => Attrs {
+            operator: "xor",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00ac => Attrs {
+            operator: "xor",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00ad => Attrs {
+            operator: "xor",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00ae => Attrs {
+            operator: "xor",
+            operands: &["(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00af => Attrs {
+            operator: "xor",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b0 => Attrs {
+            operator: "or",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b1 => Attrs {
+            operator: "or",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b2 => Attrs {
+            operator: "or",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b3 => Attrs {
+            operator: "or",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b4 => Attrs {
+            operator: "or",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b5 => Attrs {
+            operator: "or",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b6 => Attrs {
+            operator: "or",
+            operands: &["(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b7 => Attrs {
+            operator: "or",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00b8 => Attrs {
+            operator: "cp",
+            operands: &["b"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00b9 => Attrs {
+            operator: "cp",
+            operands: &["c"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00ba => Attrs {
+            operator: "cp",
+            operands: &["d"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00bb => Attrs {
+            operator: "cp",
+            operands: &["e"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00bc => Attrs {
+            operator: "cp",
+            operands: &["h"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00bd => Attrs {
+            operator: "cp",
+            operands: &["l"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00be => Attrs {
+            operator: "cp",
+            operands: &["(hl)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00bf => Attrs {
+            operator: "cp",
+            operands: &["a"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00c0 => Attrs {
+            operator: "ret",
+            operands: &["nz"],
+            size: 1,
+            time: (20, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c1 => Attrs {
+            operator: "pop",
+            operands: &["bc"],
+            size: 1,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c2 => Attrs {
+            operator: "jp",
+            operands: &["nz", "a16"],
+            size: 3,
+            time: (16, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c3 => Attrs {
+            operator: "jp",
+            operands: &["a16"],
+            size: 3,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c4 => Attrs {
+            operator: "call",
+            operands: &["nz", "a16"],
+            size: 3,
+            time: (24, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c5 => Attrs {
+            operator: "push",
+            operands: &["bc"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c6 => Attrs {
+            operator: "add",
+            operands: &["a", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00c7 => Attrs {
+            operator: "rst",
+            operands: &["0x00"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c8 => Attrs {
+            operator: "ret",
+            operands: &["z"],
+            size: 1,
+            time: (20, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00c9 => Attrs {
+            operator: "ret",
+            operands: &[],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00ca => Attrs {
+            operator: "jp",
+            operands: &["z", "a16"],
+            size: 3,
+            time: (16, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00cb => Attrs {
+            operator: "prefix",
+            operands: &["cb"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00cc => Attrs {
+            operator: "call",
+            operands: &["z", "a16"],
+            size: 3,
+            time: (24, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00cd => Attrs {
+            operator: "call",
+            operands: &["a16"],
+            size: 3,
+            time: (24, 24),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00ce => Attrs {
+            operator: "adc",
+            operands: &["a", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00cf => Attrs {
+            operator: "rst",
+            operands: &["0x08"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d0 => Attrs {
+            operator: "ret",
+            operands: &["nc"],
+            size: 1,
+            time: (20, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d1 => Attrs {
+            operator: "pop",
+            operands: &["de"],
+            size: 1,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d2 => Attrs {
+            operator: "jp",
+            operands: &["nc", "a16"],
+            size: 3,
+            time: (16, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d4 => Attrs {
+            operator: "call",
+            operands: &["nc", "a16"],
+            size: 3,
+            time: (24, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d5 => Attrs {
+            operator: "push",
+            operands: &["de"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d6 => Attrs {
+            operator: "sub",
+            operands: &["d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00d7 => Attrs {
+            operator: "rst",
+            operands: &["0x10"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d8 => Attrs {
+            operator: "ret",
+            operands: &["cf"],
+            size: 1,
+            time: (20, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00d9 => Attrs {
+            operator: "reti",
+            operands: &[],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00da => Attrs {
+            operator: "jp",
+            operands: &["cf", "a16"],
+            size: 3,
+            time: (16, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00dc => Attrs {
+            operator: "call",
+            operands: &["cf", "a16"],
+            size: 3,
+            time: (24, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00de => Attrs {
+            operator: "sbc",
+            operands: &["a", "d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00df => Attrs {
+            operator: "rst",
+            operands: &["0x18"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00e0 => Attrs {
+            operator: "ld",
+            operands: &["(0xff00+a8)", "a"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00e1 => Attrs {
+            operator: "pop",
+            operands: &["hl"],
+            size: 1,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00e2 => Attrs {
+            operator: "ld",
+            operands: &["(0xff00+c)", "a"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00e5 => Attrs {
+            operator: "push",
+            operands: &["hl"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00e6 => Attrs {
+            operator: "and",
+            operands: &["d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Reset,
+        },
+        0x00e7 => Attrs {
+            operator: "rst",
+            operands: &["0x20"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00e8 => Attrs {
+            operator: "add",
+            operands: &["sp", "r8"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Reset,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00e9 => Attrs {
+            operator: "jp",
+            operands: &["hl"],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00ea => Attrs {
+            operator: "ld",
+            operands: &["(a16)", "a"],
+            size: 3,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00ee => Attrs {
+            operator: "xor",
+            operands: &["d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00ef => Attrs {
+            operator: "rst",
+            operands: &["0x28"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f0 => Attrs {
+            operator: "ld",
+            operands: &["a", "(0xff00+a8)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f1 => Attrs {
+            operator: "pop",
+            operands: &["af"],
+            size: 1,
+            time: (12, 12),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f2 => Attrs {
+            operator: "ld",
+            operands: &["a", "(0xff00+c)"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f3 => Attrs {
+            operator: "di",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f5 => Attrs {
+            operator: "push",
+            operands: &["af"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f6 => Attrs {
+            operator: "or",
+            operands: &["d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0x00f7 => Attrs {
+            operator: "rst",
+            operands: &["0x30"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00f8 => Attrs {
+            operator: "ldhl",
+            operands: &["sp", "r8"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Reset,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00f9 => Attrs {
+            operator: "ld",
+            operands: &["sp", "hl"],
+            size: 1,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00fa => Attrs {
+            operator: "ld",
+            operands: &["a", "(a16)"],
+            size: 3,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00fb => Attrs {
+            operator: "ei",
+            operands: &[],
+            size: 1,
+            time: (4, 4),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0x00fe => Attrs {
+            operator: "cp",
+            operands: &["d8"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Set,
+            h: FlagEffect::Affected,
+            c: FlagEffect::Affected,
+        },
+        0x00ff => Attrs {
+            operator: "rst",
+            operands: &["0x38"],
+            size: 1,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb00 => Attrs {
+            operator: "rlc",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb01 => Attrs {
+            operator: "rlc",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb02 => Attrs {
+            operator: "rlc",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb03 => Attrs {
+            operator: "rlc",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb04 => Attrs {
+            operator: "rlc",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb05 => Attrs {
+            operator: "rlc",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb06 => Attrs {
+            operator: "rlc",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb07 => Attrs {
+            operator: "rlc",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb08 => Attrs {
+            operator: "rrc",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb09 => Attrs {
+            operator: "rrc",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb0a => Attrs {
+            operator: "rrc",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb0b => Attrs {
+            operator: "rrc",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb0c => Attrs {
+            operator: "rrc",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb0d => Attrs {
+            operator: "rrc",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb0e => Attrs {
+            operator: "rrc",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb0f => Attrs {
+            operator: "rrc",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb10 => Attrs {
+            operator: "rl",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb11 => Attrs {
+            operator: "rl",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb12 => Attrs {
+            operator: "rl",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb13 => Attrs {
+            operator: "rl",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb14 => Attrs {
+            operator: "rl",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb15 => Attrs {
+            operator: "rl",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb16 => Attrs {
+            operator: "rl",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb17 => Attrs {
+            operator: "rl",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb18 => Attrs {
+            operator: "rr",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb19 => Attrs {
+            operator: "rr",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb1a => Attrs {
+            operator: "rr",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb1b => Attrs {
+            operator: "rr",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb1c => Attrs {
+            operator: "rr",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb1d => Attrs {
+            operator: "rr",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb1e => Attrs {
+            operator: "rr",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb1f => Attrs {
+            operator: "rr",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb20 => Attrs {
+            operator: "sla",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb21 => Attrs {
+            operator: "sla",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb22 => Attrs {
+            operator: "sla",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb23 => Attrs {
+            operator: "sla",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb24 => Attrs {
+            operator: "sla",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb25 => Attrs {
+            operator: "sla",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb26 => Attrs {
+            operator: "sla",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb27 => Attrs {
+            operator: "sla",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb28 => Attrs {
+            operator: "sra",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb29 => Attrs {
+            operator: "sra",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb2a => Attrs {
+            operator: "sra",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb2b => Attrs {
+            operator: "sra",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb2c => Attrs {
+            operator: "sra",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb2d => Attrs {
+            operator: "sra",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb2e => Attrs {
+            operator: "sra",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb2f => Attrs {
+            operator: "sra",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb30 => Attrs {
+            operator: "swap",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb31 => Attrs {
+            operator: "swap",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb32 => Attrs {
+            operator: "swap",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb33 => Attrs {
+            operator: "swap",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb34 => Attrs {
+            operator: "swap",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb35 => Attrs {
+            operator: "swap",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb36 => Attrs {
+            operator: "swap",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb37 => Attrs {
+            operator: "swap",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Reset,
+        },
+        0xcb38 => Attrs {
+            operator: "srl",
+            operands: &["b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb39 => Attrs {
+            operator: "srl",
+            operands: &["c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb3a => Attrs {
+            operator: "srl",
+            operands: &["d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb3b => Attrs {
+            operator: "srl",
+            operands: &["e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb3c => Attrs {
+            operator: "srl",
+            operands: &["h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb3d => Attrs {
+            operator: "srl",
+            operands: &["l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb3e => Attrs {
+            operator: "srl",
+            operands: &["(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb3f => Attrs {
+            operator: "srl",
+            operands: &["a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Reset,
+            c: FlagEffect::Affected,
+        },
+        0xcb40 => Attrs {
+            operator: "bit",
+            operands: &["0", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb41 => Attrs {
+            operator: "bit",
+            operands: &["0", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb42 => Attrs {
+            operator: "bit",
+            operands: &["0", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb43 => Attrs {
+            operator: "bit",
+            operands: &["0", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb44 => Attrs {
+            operator: "bit",
+            operands: &["0", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb45 => Attrs {
+            operator: "bit",
+            operands: &["0", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb46 => Attrs {
+            operator: "bit",
+            operands: &["0", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb47 => Attrs {
+            operator: "bit",
+            operands: &["0", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb48 => Attrs {
+            operator: "bit",
+            operands: &["1", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb49 => Attrs {
+            operator: "bit",
+            operands: &["1", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb4a => Attrs {
+            operator: "bit",
+            operands: &["1", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb4b => Attrs {
+            operator: "bit",
+            operands: &["1", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb4c => Attrs {
+            operator: "bit",
+            operands: &["1", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb4d => Attrs {
+            operator: "bit",
+            operands: &["1", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb4e => Attrs {
+            operator: "bit",
+            operands: &["1", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb4f => Attrs {
+            operator: "bit",
+            operands: &["1", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb50 => Attrs {
+            operator: "bit",
+            operands: &["2", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb51 => Attrs {
+            operator: "bit",
+            operands: &["2", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb52 => Attrs {
+            operator: "bit",
+            operands: &["2", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb53 => Attrs {
+            operator: "bit",
+            operands: &["2", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb54 => Attrs {
+            operator: "bit",
+            operands: &["2", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb55 => Attrs {
+            operator: "bit",
+            operands: &["2", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb56 => Attrs {
+            operator: "bit",
+            operands: &["2", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb57 => Attrs {
+            operator: "bit",
+            operands: &["2", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb58 => Attrs {
+            operator: "bit",
+            operands: &["3", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb59 => Attrs {
+            operator: "bit",
+            operands: &["3", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb5a => Attrs {
+            operator: "bit",
+            operands: &["3", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb5b => Attrs {
+            operator: "bit",
+            operands: &["3", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb5c => Attrs {
+            operator: "bit",
+            operands: &["3", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb5d => Attrs {
+            operator: "bit",
+            operands: &["3", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb5e => Attrs {
+            operator: "bit",
+            operands: &["3", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb5f => Attrs {
+            operator: "bit",
+            operands: &["3", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb60 => Attrs {
+            operator: "bit",
+            operands: &["4", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb61 => Attrs {
+            operator: "bit",
+            operands: &["4", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb62 => Attrs {
+            operator: "bit",
+            operands: &["4", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb63 => Attrs {
+            operator: "bit",
+            operands: &["4", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb64 => Attrs {
+            operator: "bit",
+            operands: &["4", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb65 => Attrs {
+            operator: "bit",
+            operands: &["4", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb66 => Attrs {
+            operator: "bit",
+            operands: &["4", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb67 => Attrs {
+            operator: "bit",
+            operands: &["4", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb68 => Attrs {
+            operator: "bit",
+            operands: &["5", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb69 => Attrs {
+            operator: "bit",
+            operands: &["5", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb6a => Attrs {
+            operator: "bit",
+            operands: &["5", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb6b => Attrs {
+            operator: "bit",
+            operands: &["5", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb6c => Attrs {
+            operator: "bit",
+            operands: &["5", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb6d => Attrs {
+            operator: "bit",
+            operands: &["5", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb6e => Attrs {
+            operator: "bit",
+            operands: &["5", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb6f => Attrs {
+            operator: "bit",
+            operands: &["5", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb70 => Attrs {
+            operator: "bit",
+            operands: &["6", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb71 => Attrs {
+            operator: "bit",
+            operands: &["6", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb72 => Attrs {
+            operator: "bit",
+            operands: &["6", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb73 => Attrs {
+            operator: "bit",
+            operands: &["6", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb74 => Attrs {
+            operator: "bit",
+            operands: &["6", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb75 => Attrs {
+            operator: "bit",
+            operands: &["6", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb76 => Attrs {
+            operator: "bit",
+            operands: &["6", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb77 => Attrs {
+            operator: "bit",
+            operands: &["6", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb78 => Attrs {
+            operator: "bit",
+            operands: &["7", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb79 => Attrs {
+            operator: "bit",
+            operands: &["7", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb7a => Attrs {
+            operator: "bit",
+            operands: &["7", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb7b => Attrs {
+            operator: "bit",
+            operands: &["7", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb7c => Attrs {
+            operator: "bit",
+            operands: &["7", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb7d => Attrs {
+            operator: "bit",
+            operands: &["7", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb7e => Attrs {
+            operator: "bit",
+            operands: &["7", "(hl)"],
+            size: 2,
+            time: (12, 12),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb7f => Attrs {
+            operator: "bit",
+            operands: &["7", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Affected,
+            n: FlagEffect::Reset,
+            h: FlagEffect::Set,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb80 => Attrs {
+            operator: "res",
+            operands: &["0", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb81 => Attrs {
+            operator: "res",
+            operands: &["0", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb82 => Attrs {
+            operator: "res",
+            operands: &["0", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb83 => Attrs {
+            operator: "res",
+            operands: &["0", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb84 => Attrs {
+            operator: "res",
+            operands: &["0", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb85 => Attrs {
+            operator: "res",
+            operands: &["0", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb86 => Attrs {
+            operator: "res",
+            operands: &["0", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb87 => Attrs {
+            operator: "res",
+            operands: &["0", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb88 => Attrs {
+            operator: "res",
+            operands: &["1", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb89 => Attrs {
+            operator: "res",
+            operands: &["1", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb8a => Attrs {
+            operator: "res",
+            operands: &["1", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb8b => Attrs {
+            operator: "res",
+            operands: &["1", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb8c => Attrs {
+            operator: "res",
+            operands: &["1", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb8d => Attrs {
+            operator: "res",
+            operands: &["1", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb8e => Attrs {
+            operator: "res",
+            operands: &["1", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb8f => Attrs {
+            operator: "res",
+            operands: &["1", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb90 => Attrs {
+            operator: "res",
+            operands: &["2", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb91 => Attrs {
+            operator: "res",
+            operands: &["2", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb92 => Attrs {
+            operator: "res",
+            operands: &["2", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb93 => Attrs {
+            operator: "res",
+            operands: &["2", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb94 => Attrs {
+            operator: "res",
+            operands: &["2", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb95 => Attrs {
+            operator: "res",
+            operands: &["2", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb96 => Attrs {
+            operator: "res",
+            operands: &["2", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb97 => Attrs {
+            operator: "res",
+            operands: &["2", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb98 => Attrs {
+            operator: "res",
+            operands: &["3", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb99 => Attrs {
+            operator: "res",
+            operands: &["3", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb9a => Attrs {
+            operator: "res",
+            operands: &["3", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb9b => Attrs {
+            operator: "res",
+            operands: &["3", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb9c => Attrs {
+            operator: "res",
+            operands: &["3", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb9d => Attrs {
+            operator: "res",
+            operands: &["3", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb9e => Attrs {
+            operator: "res",
+            operands: &["3", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcb9f => Attrs {
+            operator: "res",
+            operands: &["3", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba0 => Attrs {
+            operator: "res",
+            operands: &["4", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba1 => Attrs {
+            operator: "res",
+            operands: &["4", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba2 => Attrs {
+            operator: "res",
+            operands: &["4", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba3 => Attrs {
+            operator: "res",
+            operands: &["4", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba4 => Attrs {
+            operator: "res",
+            operands: &["4", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba5 => Attrs {
+            operator: "res",
+            operands: &["4", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba6 => Attrs {
+            operator: "res",
+            operands: &["4", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba7 => Attrs {
+            operator: "res",
+            operands: &["4", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba8 => Attrs {
+            operator: "res",
+            operands: &["5", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcba9 => Attrs {
+            operator: "res",
+            operands: &["5", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbaa => Attrs {
+            operator: "res",
+            operands: &["5", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbab => Attrs {
+            operator: "res",
+            operands: &["5", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbac => Attrs {
+            operator: "res",
+            operands: &["5", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbad => Attrs {
+            operator: "res",
+            operands: &["5", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbae => Attrs {
+            operator: "res",
+            operands: &["5", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbaf => Attrs {
+            operator: "res",
+            operands: &["5", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb0 => Attrs {
+            operator: "res",
+            operands: &["6", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb1 => Attrs {
+            operator: "res",
+            operands: &["6", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb2 => Attrs {
+            operator: "res",
+            operands: &["6", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb3 => Attrs {
+            operator: "res",
+            operands: &["6", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb4 => Attrs {
+            operator: "res",
+            operands: &["6", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb5 => Attrs {
+            operator: "res",
+            operands: &["6", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb6 => Attrs {
+            operator: "res",
+            operands: &["6", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb7 => Attrs {
+            operator: "res",
+            operands: &["6", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb8 => Attrs {
+            operator: "res",
+            operands: &["7", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbb9 => Attrs {
+            operator: "res",
+            operands: &["7", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbba => Attrs {
+            operator: "res",
+            operands: &["7", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbbb => Attrs {
+            operator: "res",
+            operands: &["7", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbbc => Attrs {
+            operator: "res",
+            operands: &["7", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbbd => Attrs {
+            operator: "res",
+            operands: &["7", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbbe => Attrs {
+            operator: "res",
+            operands: &["7", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbbf => Attrs {
+            operator: "res",
+            operands: &["7", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc0 => Attrs {
+            operator: "set",
+            operands: &["0", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc1 => Attrs {
+            operator: "set",
+            operands: &["0", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc2 => Attrs {
+            operator: "set",
+            operands: &["0", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc3 => Attrs {
+            operator: "set",
+            operands: &["0", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc4 => Attrs {
+            operator: "set",
+            operands: &["0", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc5 => Attrs {
+            operator: "set",
+            operands: &["0", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc6 => Attrs {
+            operator: "set",
+            operands: &["0", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc7 => Attrs {
+            operator: "set",
+            operands: &["0", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc8 => Attrs {
+            operator: "set",
+            operands: &["1", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbc9 => Attrs {
+            operator: "set",
+            operands: &["1", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbca => Attrs {
+            operator: "set",
+            operands: &["1", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbcb => Attrs {
+            operator: "set",
+            operands: &["1", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbcc => Attrs {
+            operator: "set",
+            operands: &["1", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbcd => Attrs {
+            operator: "set",
+            operands: &["1", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbce => Attrs {
+            operator: "set",
+            operands: &["1", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbcf => Attrs {
+            operator: "set",
+            operands: &["1", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd0 => Attrs {
+            operator: "set",
+            operands: &["2", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd1 => Attrs {
+            operator: "set",
+            operands: &["2", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd2 => Attrs {
+            operator: "set",
+            operands: &["2", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd3 => Attrs {
+            operator: "set",
+            operands: &["2", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd4 => Attrs {
+            operator: "set",
+            operands: &["2", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd5 => Attrs {
+            operator: "set",
+            operands: &["2", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd6 => Attrs {
+            operator: "set",
+            operands: &["2", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd7 => Attrs {
+            operator: "set",
+            operands: &["2", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd8 => Attrs {
+            operator: "set",
+            operands: &["3", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbd9 => Attrs {
+            operator: "set",
+            operands: &["3", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbda => Attrs {
+            operator: "set",
+            operands: &["3", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbdb => Attrs {
+            operator: "set",
+            operands: &["3", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbdc => Attrs {
+            operator: "set",
+            operands: &["3", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbdd => Attrs {
+            operator: "set",
+            operands: &["3", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbde => Attrs {
+            operator: "set",
+            operands: &["3", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbdf => Attrs {
+            operator: "set",
+            operands: &["3", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe0 => Attrs {
+            operator: "set",
+            operands: &["4", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe1 => Attrs {
+            operator: "set",
+            operands: &["4", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe2 => Attrs {
+            operator: "set",
+            operands: &["4", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe3 => Attrs {
+            operator: "set",
+            operands: &["4", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe4 => Attrs {
+            operator: "set",
+            operands: &["4", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe5 => Attrs {
+            operator: "set",
+            operands: &["4", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe6 => Attrs {
+            operator: "set",
+            operands: &["4", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe7 => Attrs {
+            operator: "set",
+            operands: &["4", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe8 => Attrs {
+            operator: "set",
+            operands: &["5", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbe9 => Attrs {
+            operator: "set",
+            operands: &["5", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbea => Attrs {
+            operator: "set",
+            operands: &["5", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbeb => Attrs {
+            operator: "set",
+            operands: &["5", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbec => Attrs {
+            operator: "set",
+            operands: &["5", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbed => Attrs {
+            operator: "set",
+            operands: &["5", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbee => Attrs {
+            operator: "set",
+            operands: &["5", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbef => Attrs {
+            operator: "set",
+            operands: &["5", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf0 => Attrs {
+            operator: "set",
+            operands: &["6", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf1 => Attrs {
+            operator: "set",
+            operands: &["6", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf2 => Attrs {
+            operator: "set",
+            operands: &["6", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf3 => Attrs {
+            operator: "set",
+            operands: &["6", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf4 => Attrs {
+            operator: "set",
+            operands: &["6", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf5 => Attrs {
+            operator: "set",
+            operands: &["6", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf6 => Attrs {
+            operator: "set",
+            operands: &["6", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf7 => Attrs {
+            operator: "set",
+            operands: &["6", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf8 => Attrs {
+            operator: "set",
+            operands: &["7", "b"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbf9 => Attrs {
+            operator: "set",
+            operands: &["7", "c"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbfa => Attrs {
+            operator: "set",
+            operands: &["7", "d"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbfb => Attrs {
+            operator: "set",
+            operands: &["7", "e"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbfc => Attrs {
+            operator: "set",
+            operands: &["7", "h"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbfd => Attrs {
+            operator: "set",
+            operands: &["7", "l"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbfe => Attrs {
+            operator: "set",
+            operands: &["7", "(hl)"],
+            size: 2,
+            time: (16, 16),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        0xcbff => Attrs {
+            operator: "set",
+            operands: &["7", "a"],
+            size: 2,
+            time: (8, 8),
+            z: FlagEffect::Unaffected,
+            n: FlagEffect::Unaffected,
+            h: FlagEffect::Unaffected,
+            c: FlagEffect::Unaffected,
+        },
+        _ => return None,
+    };
+
+    Some(attrs)
+}
+
 /// Decodes the opecode and actually executes one instruction.
 impl<T: Sys> Cpu<T> {
     /// Execute the instruction returning the expected consumed cycles
     pub fn decode(&mut self, code: u16) -> usize {
         trace!("{:04x}: {:04x}: {}", self.get_pc(), code, mnem(code));
 
-        let time = match code {
+        match code {
             0x0000 => self.op_0000(),
             0x0001 => self.op_0001(),
             0x0002 => self.op_0002(),
@@ -7263,12 +12296,7 @@ impl<T: Sys> Cpu<T> {
             0xcbfd => self.op_cbfd(),
             0xcbfe => self.op_cbfe(),
             0xcbff => self.op_cbff(),
-            _ => panic!("Invalid opcode: {:04x}: {:04x}", self.get_pc(), code),
-        };
-
-        // Every instruction consumes at least 4 cycles.
-        self.step(4);
-
-        time
+            _ => self.lock_up(code),
+        }
     }
 }
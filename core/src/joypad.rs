@@ -55,6 +55,11 @@ impl Joypad {
         value
     }
 
+    /// Check if any of the selected lines is low.
+    pub(crate) fn pressed(&self) -> bool {
+        self.check() != 0x0f
+    }
+
     pub(crate) fn read(&self) -> u8 {
         debug!("Joypad read: dir: {:02x}", self.select);
         self.check()
@@ -133,9 +133,15 @@ pub mod debug;
 /// Adaptor to register devices to MMU.
 // pub mod device;
 
+/// Disassembler which decodes instructions with the operand values.
+pub mod disasm;
+
 /// Decoder which evaluates each CPU instructions.
 pub mod inst;
 
+/// Cartridge mapper interface.
+pub mod mapper;
+
 /// Handles memory and I/O port access from the CPU.
 pub mod mmu;
 
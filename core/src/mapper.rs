@@ -0,0 +1,120 @@
+use alloc::{boxed::Box, vec::Vec};
+use hashbrown::HashMap;
+
+/// Cartridge mapper (memory bank controller) interface.
+///
+/// A mapper handles all the CPU accesses to the cartridge address space,
+/// i.e. ROM (0x0000 - 0x7fff) and external RAM (0xa000 - 0xbfff).
+/// The built-in MBCs implement this trait, and custom mappers for
+/// flash carts or homebrew hardware can be plugged in through [`crate::Config`].
+pub trait Mapper {
+    /// The name of the mapper shown in the cartridge information.
+    fn name(&self) -> &str {
+        "Custom"
+    }
+
+    /// Read a byte from the cartridge address space.
+    fn read(&self, addr: u16) -> u8;
+
+    /// Write a byte to the cartridge address space.
+    fn write(&mut self, addr: u16, value: u8);
+
+    /// The ROM bank currently mapped to 0x4000 - 0x7fff, which is used by the debugger.
+    fn rom_bank(&self) -> usize {
+        1
+    }
+
+    /// Proceed the mapper state by the given CPU cycles.
+    fn step(&mut self, _cycles: usize) {}
+
+    /// Flush the battery-backed RAM to the persistent storage.
+    fn save(&mut self) {}
+
+    /// Serialize the mapper state (bank registers, RAM, etc.) for save states.
+    fn save_state(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// Restore the mapper state serialized by [`Mapper::save_state`].
+    fn load_state(&mut self, _state: &[u8]) {}
+}
+
+/// Function to create a mapper from the ROM content.
+pub type MapperFactory = Box<dyn Fn(&[u8]) -> Box<dyn Mapper>>;
+
+/// Set of custom mappers keyed by the cartridge type byte (0x147 in the ROM header).
+///
+/// Mappers registered here take precedence over the built-in ones.
+#[derive(Default)]
+pub struct MapperRegistry {
+    factories: HashMap<u8, MapperFactory>,
+}
+
+impl MapperRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a mapper factory for the cartridge type.
+    /// The factory replaces the one previously registered for the same type if any.
+    pub fn register<F>(&mut self, code: u8, factory: F)
+    where
+        F: Fn(&[u8]) -> Box<dyn Mapper> + 'static,
+    {
+        self.factories.insert(code, Box::new(factory));
+    }
+
+    /// Check if a mapper is registered for the cartridge type.
+    pub fn contains(&self, code: u8) -> bool {
+        self.factories.contains_key(&code)
+    }
+
+    /// Create the mapper registered for the cartridge type if any.
+    pub fn create(&self, code: u8, rom: &[u8]) -> Option<Box<dyn Mapper>> {
+        self.factories.get(&code).map(|f| f(rom))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Flash {
+        rom: Vec<u8>,
+    }
+
+    impl Mapper for Flash {
+        fn name(&self) -> &str {
+            "Flash"
+        }
+
+        fn read(&self, addr: u16) -> u8 {
+            self.rom[addr as usize]
+        }
+
+        fn write(&mut self, addr: u16, value: u8) {
+            self.rom[addr as usize] = value;
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut reg = MapperRegistry::new();
+
+        assert!(!reg.contains(0xfc));
+        assert!(reg.create(0xfc, &[0; 0x10]).is_none());
+
+        reg.register(0xfc, |rom| Box::new(Flash { rom: rom.to_vec() }));
+
+        assert!(reg.contains(0xfc));
+        assert!(!reg.contains(0x00));
+
+        let mut m = reg.create(0xfc, &[0x12; 0x10]).unwrap();
+        assert_eq!(m.name(), "Flash");
+        assert_eq!(m.read(0x3), 0x12);
+        m.write(0x3, 0x34);
+        assert_eq!(m.read(0x3), 0x34);
+        assert!(m.save_state().is_empty());
+    }
+}
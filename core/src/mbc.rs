@@ -1,5 +1,7 @@
 use crate::hardware::HardwareHandle;
+use crate::mapper::Mapper;
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     vec,
     vec::Vec,
@@ -21,8 +23,14 @@ impl MbcNone {
             ram: vec![0; 0x2000],
         }
     }
+}
 
-    fn on_read(&self, addr: u16) -> u8 {
+impl Mapper for MbcNone {
+    fn name(&self) -> &str {
+        "None"
+    }
+
+    fn read(&self, addr: u16) -> u8 {
         match addr {
             0x0000..=0x7fff => self.rom[addr as usize],
             0xa000..=0xbfff => self.ram[addr as usize - 0xa000],
@@ -30,13 +38,23 @@ impl MbcNone {
         }
     }
 
-    fn on_write(&mut self, addr: u16, value: u8) {
+    fn write(&mut self, addr: u16, value: u8) {
         match addr {
             0x0000..=0x7fff => self.rom[addr as usize] = value,
             0xa000..=0xbfff => self.ram[addr as usize - 0xa000] = value,
             _ => unreachable!("write attempt to mbc0 addr={:04x}, v={:02x}", addr, value),
         }
     }
+
+    fn save_state(&self) -> Vec<u8> {
+        self.ram.clone()
+    }
+
+    fn load_state(&mut self, state: &[u8]) {
+        if let Some((_, ram)) = split_state(state, 0, self.ram.len()) {
+            self.ram.copy_from_slice(ram);
+        }
+    }
 }
 
 struct Mbc1 {
@@ -63,22 +81,32 @@ impl Mbc1 {
             ram_select: false,
         }
     }
+}
 
-    fn on_read(&self, addr: u16) -> u8 {
+impl Mapper for Mbc1 {
+    fn name(&self) -> &str {
+        "Mbc1"
+    }
+
+    fn rom_bank(&self) -> usize {
+        let rom_bank = self.rom_bank.max(1);
+
+        // ROM bank 0x20, 0x40, 0x60 are somehow not available
+        let rom_bank = if rom_bank == 0x20 || rom_bank == 0x40 || rom_bank == 0x60 {
+            warn!("Odd ROM bank selection: {:02x}", rom_bank);
+            rom_bank + 1
+        } else {
+            rom_bank
+        };
+
+        rom_bank & (self.rom.len() / 0x4000 - 1)
+    }
+
+    fn read(&self, addr: u16) -> u8 {
         if addr <= 0x3fff {
             self.rom[addr as usize]
         } else if (0x4000..=0x7fff).contains(&addr) {
-            let rom_bank = self.rom_bank.max(1);
-
-            // ROM bank 0x20, 0x40, 0x60 are somehow not available
-            let rom_bank = if rom_bank == 0x20 || rom_bank == 0x40 || rom_bank == 0x60 {
-                warn!("Odd ROM bank selection: {:02x}", rom_bank);
-                rom_bank + 1
-            } else {
-                rom_bank
-            };
-
-            let base = rom_bank * 0x4000;
+            let base = self.rom_bank() * 0x4000;
             let offset = addr as usize - 0x4000;
             let addr = (base + offset) & (self.rom.len() - 1);
             self.rom[addr]
@@ -97,7 +125,7 @@ impl Mbc1 {
         }
     }
 
-    fn on_write(&mut self, addr: u16, value: u8) {
+    fn write(&mut self, addr: u16, value: u8) {
         if addr <= 0x1fff {
             if value & 0xf == 0x0a {
                 debug!("External RAM enabled");
@@ -105,7 +133,7 @@ impl Mbc1 {
             } else {
                 debug!("External RAM disabled");
                 self.ram_enable = false;
-                self.hw.get().borrow_mut().save_ram(&self.ram);
+                self.save();
             }
         } else if (0x2000..=0x3fff).contains(&addr) {
             self.rom_bank = (self.rom_bank & !0x1f) | (value as usize & 0x1f);
@@ -117,13 +145,8 @@ impl Mbc1 {
                 self.rom_bank = (self.rom_bank & !0x60) | ((value as usize & 0x3) << 5);
             }
         } else if (0x6000..=0x7fff).contains(&addr) {
-            if value == 0x00 {
-                self.ram_select = false;
-            } else if value == 0x01 {
-                self.ram_select = true;
-            } else {
-                unimplemented!("Invalid ROM/RAM select mode");
-            }
+            // Only bit 0 selects the mode; the other bits are ignored.
+            self.ram_select = value & 0x01 != 0;
         } else if (0xa000..=0xbfff).contains(&addr) {
             if self.ram_enable {
                 let base = self.ram_bank * 0x2000;
@@ -136,6 +159,31 @@ impl Mbc1 {
             unimplemented!("write to rom {:04x} {:02x}", addr, value)
         }
     }
+
+    fn save(&mut self) {
+        self.hw.get().borrow_mut().save_ram(&self.ram);
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = vec![
+            self.rom_bank as u8,
+            self.ram_bank as u8,
+            self.ram_enable as u8,
+            self.ram_select as u8,
+        ];
+        state.extend_from_slice(&self.ram);
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) {
+        if let Some((regs, ram)) = split_state(state, 4, self.ram.len()) {
+            self.rom_bank = regs[0] as usize;
+            self.ram_bank = regs[1] as usize;
+            self.ram_enable = regs[2] != 0;
+            self.ram_select = regs[3] != 0;
+            self.ram.copy_from_slice(ram);
+        }
+    }
 }
 
 struct Mbc2 {
@@ -158,12 +206,22 @@ impl Mbc2 {
             ram_enable: false,
         }
     }
+}
+
+impl Mapper for Mbc2 {
+    fn name(&self) -> &str {
+        "Mbc2"
+    }
 
-    fn on_read(&self, addr: u16) -> u8 {
+    fn rom_bank(&self) -> usize {
+        self.rom_bank.max(1)
+    }
+
+    fn read(&self, addr: u16) -> u8 {
         if addr <= 0x3fff {
             self.rom[addr as usize]
         } else if (0x4000..=0x7fff).contains(&addr) {
-            let base = self.rom_bank.max(1) * 0x4000;
+            let base = self.rom_bank() * 0x4000;
             let offset = addr as usize - 0x4000;
             self.rom[base + offset]
         } else if (0xa000..=0xa1ff).contains(&addr) {
@@ -178,7 +236,7 @@ impl Mbc2 {
         }
     }
 
-    fn on_write(&mut self, addr: u16, value: u8) {
+    fn write(&mut self, addr: u16, value: u8) {
         if addr <= 0x1fff {
             if addr & 0x100 == 0 {
                 self.ram_enable = (value & 0x0f) == 0x0a;
@@ -192,7 +250,7 @@ impl Mbc2 {
                     value
                 );
                 if !self.ram_enable {
-                    self.hw.get().borrow_mut().save_ram(&self.ram);
+                    self.save();
                 }
             }
         } else if (0x2000..=0x3fff).contains(&addr) {
@@ -212,6 +270,24 @@ impl Mbc2 {
             warn!("write to rom {:04x} {:02x}", addr, value);
         }
     }
+
+    fn save(&mut self) {
+        self.hw.get().borrow_mut().save_ram(&self.ram);
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = vec![self.rom_bank as u8, self.ram_enable as u8];
+        state.extend_from_slice(&self.ram);
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) {
+        if let Some((regs, ram)) = split_state(state, 2, self.ram.len()) {
+            self.rom_bank = regs[0] as usize;
+            self.ram_enable = regs[1] != 0;
+            self.ram.copy_from_slice(ram);
+        }
+    }
 }
 
 struct Mbc3 {
@@ -230,12 +306,6 @@ struct Mbc3 {
     prelatch: bool,
 }
 
-impl Drop for Mbc3 {
-    fn drop(&mut self) {
-        self.save();
-    }
-}
-
 impl Mbc3 {
     fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
         let ram = hw.get().borrow_mut().load_ram(0x8000);
@@ -259,20 +329,25 @@ impl Mbc3 {
         s
     }
 
-    fn save(&mut self) {
-        self.hw.get().borrow_mut().save_ram(&self.ram);
-    }
-
     fn epoch(&self) -> u64 {
         self.hw.get().borrow_mut().clock() / 1_000_000
     }
+}
 
-    fn on_read(&self, addr: u16) -> u8 {
+impl Mapper for Mbc3 {
+    fn name(&self) -> &str {
+        "Mbc3"
+    }
+
+    fn rom_bank(&self) -> usize {
+        self.rom_bank.max(1)
+    }
+
+    fn read(&self, addr: u16) -> u8 {
         if addr <= 0x3fff {
             self.rom[addr as usize]
         } else if (0x4000..=0x7fff).contains(&addr) {
-            let rom_bank = self.rom_bank.max(1);
-            let base = rom_bank * 0x4000;
+            let base = self.rom_bank() * 0x4000;
             let offset = addr as usize - 0x4000;
             self.rom[base + offset]
         } else if (0xa000..=0xbfff).contains(&addr) {
@@ -287,18 +362,22 @@ impl Mbc3 {
                 0x0a => self.rtc_hours,
                 0x0b => self.rtc_day_low,
                 0x0c => self.rtc_day_high,
-                s => unimplemented!("Unknown selector: {:02x}", s),
+                s => {
+                    warn!("Read from unknown RAM bank/RTC: {:02x}", s);
+                    0xff
+                }
             }
         } else {
             unreachable!("Invalid read from ROM: {:02x}", addr);
         }
     }
 
-    fn on_write(&mut self, addr: u16, value: u8) {
+    fn write(&mut self, addr: u16, value: u8) {
         if addr <= 0x1fff {
             if value == 0x00 {
                 info!("External RAM/RTC disabled");
                 self.enable = false;
+                self.save();
             } else if value == 0x0a {
                 info!("External RAM/RTC enabled");
                 self.enable = true;
@@ -346,13 +425,54 @@ impl Mbc3 {
                     self.rtc_day_high = value;
                     self.update_epoch();
                 }
-                s => unimplemented!("Unknown selector: {:02x}", s),
+                s => warn!("Write to unknown RAM bank/RTC: {:02x} {:02x}", s, value),
             }
         } else {
             unimplemented!("write to rom {:04x} {:02x}", addr, value)
         }
     }
 
+    fn save(&mut self) {
+        self.hw.get().borrow_mut().save_ram(&self.ram);
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = vec![
+            self.rom_bank as u8,
+            self.enable as u8,
+            self.select,
+            self.rtc_secs,
+            self.rtc_mins,
+            self.rtc_hours,
+            self.rtc_day_low,
+            self.rtc_day_high,
+            self.prelatch as u8,
+        ];
+        state.extend_from_slice(&self.epoch.to_le_bytes());
+        state.extend_from_slice(&self.ram);
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) {
+        if let Some((regs, ram)) = split_state(state, 17, self.ram.len()) {
+            self.rom_bank = regs[0] as usize;
+            self.enable = regs[1] != 0;
+            self.select = regs[2];
+            self.rtc_secs = regs[3];
+            self.rtc_mins = regs[4];
+            self.rtc_hours = regs[5];
+            self.rtc_day_low = regs[6];
+            self.rtc_day_high = regs[7];
+            self.prelatch = regs[8] != 0;
+            let mut epoch = [0; 8];
+            epoch.copy_from_slice(&regs[9..17]);
+            self.epoch = u64::from_le_bytes(epoch);
+            self.ram.copy_from_slice(ram);
+        }
+    }
+}
+
+impl Mbc3 {
     fn update_epoch(&mut self) {
         self.epoch = self.epoch();
     }
@@ -434,8 +554,18 @@ impl Mbc5 {
             ram_enable: false,
         }
     }
+}
 
-    fn on_read(&self, addr: u16) -> u8 {
+impl Mapper for Mbc5 {
+    fn name(&self) -> &str {
+        "Mbc5"
+    }
+
+    fn rom_bank(&self) -> usize {
+        self.rom_bank
+    }
+
+    fn read(&self, addr: u16) -> u8 {
         if addr <= 0x3fff {
             self.rom[addr as usize]
         } else if (0x4000..=0x7fff).contains(&addr) {
@@ -456,7 +586,7 @@ impl Mbc5 {
         }
     }
 
-    fn on_write(&mut self, addr: u16, value: u8) {
+    fn write(&mut self, addr: u16, value: u8) {
         if addr <= 0x1fff {
             if value & 0xf == 0x0a {
                 info!("External RAM enabled");
@@ -464,7 +594,7 @@ impl Mbc5 {
             } else {
                 info!("External RAM disabled");
                 self.ram_enable = false;
-                self.hw.get().borrow_mut().save_ram(&self.ram);
+                self.save();
             }
         } else if (0x2000..=0x2fff).contains(&addr) {
             self.rom_bank = (self.rom_bank & !0xff) | value as usize;
@@ -486,6 +616,30 @@ impl Mbc5 {
             unimplemented!("write to rom {:04x} {:02x}", addr, value)
         }
     }
+
+    fn save(&mut self) {
+        self.hw.get().borrow_mut().save_ram(&self.ram);
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = vec![
+            self.rom_bank as u8,
+            (self.rom_bank >> 8) as u8,
+            self.ram_bank as u8,
+            self.ram_enable as u8,
+        ];
+        state.extend_from_slice(&self.ram);
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) {
+        if let Some((regs, ram)) = split_state(state, 4, self.ram.len()) {
+            self.rom_bank = regs[0] as usize | (regs[1] as usize) << 8;
+            self.ram_bank = regs[2] as usize;
+            self.ram_enable = regs[3] != 0;
+            self.ram.copy_from_slice(ram);
+        }
+    }
 }
 
 #[allow(unused)]
@@ -497,79 +651,50 @@ impl HuC1 {
     fn new(rom: Vec<u8>) -> Self {
         Self { rom }
     }
+}
 
-    fn on_read(&self, _addr: u16) -> u8 {
-        unimplemented!()
+impl Mapper for HuC1 {
+    fn name(&self) -> &str {
+        "HuC1"
     }
 
-    fn on_write(&mut self, _addr: u16, _value: u8) {
+    fn read(&self, _addr: u16) -> u8 {
         unimplemented!()
     }
-}
-
-enum MbcType {
-    None(MbcNone),
-    Mbc1(Mbc1),
-    Mbc2(Mbc2),
-    Mbc3(Mbc3),
-    Mbc5(Mbc5),
-    HuC1(HuC1),
-}
-
-impl MbcType {
-    fn new(hw: HardwareHandle, code: u8, rom: Vec<u8>) -> Self {
-        match code {
-            0x00 => MbcType::None(MbcNone::new(rom)),
-            0x01..=0x03 => MbcType::Mbc1(Mbc1::new(hw, rom)),
-            0x05 | 0x06 => MbcType::Mbc2(Mbc2::new(hw, rom)),
-            0x08 | 0x09 => unimplemented!("ROM+RAM: {:02x}", code),
-            0x0b..=0x0d => unimplemented!("MMM01: {:02x}", code),
-            0x0f..=0x13 => MbcType::Mbc3(Mbc3::new(hw, rom)),
-            0x15..=0x17 => unimplemented!("Mbc4: {:02x}", code),
-            0x19..=0x1e => MbcType::Mbc5(Mbc5::new(hw, rom)),
-            0xfc => unimplemented!("POCKET CAMERA"),
-            0xfd => unimplemented!("BANDAI TAMAS"),
-            0xfe => unimplemented!("HuC3"),
-            0xff => MbcType::HuC1(HuC1::new(rom)),
-            _ => unreachable!("Invalid cartridge type: {:02x}", code),
-        }
-    }
 
-    fn on_read(&self, addr: u16) -> u8 {
-        match self {
-            MbcType::None(c) => c.on_read(addr),
-            MbcType::Mbc1(c) => c.on_read(addr),
-            MbcType::Mbc2(c) => c.on_read(addr),
-            MbcType::Mbc3(c) => c.on_read(addr),
-            MbcType::Mbc5(c) => c.on_read(addr),
-            MbcType::HuC1(c) => c.on_read(addr),
-        }
+    fn write(&mut self, _addr: u16, _value: u8) {
+        unimplemented!()
     }
+}
 
-    fn on_write(&mut self, addr: u16, value: u8) {
-        match self {
-            MbcType::None(c) => c.on_write(addr, value),
-            MbcType::Mbc1(c) => c.on_write(addr, value),
-            MbcType::Mbc2(c) => c.on_write(addr, value),
-            MbcType::Mbc3(c) => c.on_write(addr, value),
-            MbcType::Mbc5(c) => c.on_write(addr, value),
-            MbcType::HuC1(c) => c.on_write(addr, value),
-        }
+fn split_state(state: &[u8], regs: usize, ram: usize) -> Option<(&[u8], &[u8])> {
+    if state.len() == regs + ram {
+        Some(state.split_at(regs))
+    } else {
+        warn!(
+            "Mapper state size mismatch: expect: {}, actual: {}",
+            regs + ram,
+            state.len()
+        );
+        None
     }
 }
 
-impl alloc::fmt::Display for MbcType {
-    fn fmt(&self, f: &mut alloc::fmt::Formatter) -> alloc::fmt::Result {
-        let name = match self {
-            MbcType::None(_) => "None",
-            MbcType::Mbc1(_) => "Mbc1",
-            MbcType::Mbc2(_) => "Mbc2",
-            MbcType::Mbc3(_) => "Mbc3",
-            MbcType::Mbc5(_) => "Mbc5",
-            MbcType::HuC1(_) => "HuC1",
-        };
-
-        write!(f, "{}", name)
+fn new_mapper(hw: HardwareHandle, code: u8, rom: Vec<u8>) -> Box<dyn Mapper> {
+    match code {
+        0x00 => Box::new(MbcNone::new(rom)),
+        0x01..=0x03 => Box::new(Mbc1::new(hw, rom)),
+        0x05 | 0x06 => Box::new(Mbc2::new(hw, rom)),
+        0x08 | 0x09 => unimplemented!("ROM+RAM: {:02x}", code),
+        0x0b..=0x0d => unimplemented!("MMM01: {:02x}", code),
+        0x0f..=0x13 => Box::new(Mbc3::new(hw, rom)),
+        0x15..=0x17 => unimplemented!("Mbc4: {:02x}", code),
+        0x19..=0x1e => Box::new(Mbc5::new(hw, rom)),
+        0xfc => unimplemented!("POCKET CAMERA"),
+        0xfd => unimplemented!("BANDAI TAMAS"),
+        0xfe => unimplemented!("HuC3"),
+        0xff => Box::new(HuC1::new(rom)),
+        _ => unreachable!("Invalid cartridge type: {:02x}", code),
     }
 }
 
@@ -589,7 +714,7 @@ struct Cartridge {
     license_new: String,
     license_old: u8,
     sgb: bool,
-    mbc: MbcType,
+    mbc: Box<dyn Mapper>,
     rom_size: u8,
     ram_size: u8,
     dstcode: u8,
@@ -617,7 +742,7 @@ fn verify(rom: &[u8], checksum: u16) {
 }
 
 impl Cartridge {
-    fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
+    fn new(hw: HardwareHandle, rom: Vec<u8>, mapper: Option<Box<dyn Mapper>>) -> Self {
         let checksum = (rom[0x14e] as u16) << 8 | (rom[0x14f] as u16);
 
         verify(&rom, checksum);
@@ -629,7 +754,7 @@ impl Cartridge {
             license_new: parse_str(&rom[0x144..0x146]),
             license_old: rom[0x14b],
             sgb: rom[0x146] == 0x03,
-            mbc: MbcType::new(hw, rom[0x147], rom.clone()),
+            mbc: mapper.unwrap_or_else(|| new_mapper(hw, rom[0x147], rom.clone())),
             rom_size: rom[0x148],
             ram_size: rom[0x149],
             dstcode: rom[0x14a],
@@ -650,7 +775,7 @@ impl Cartridge {
         };
         info!("Destination: {}", dstcode);
 
-        info!("Mbc: {}", self.mbc);
+        info!("Mbc: {}", self.mbc.name());
         info!(
             "Color: {} (Compat: {}), Super: {}",
             self.cgb, !self.cgb_only, self.sgb,
@@ -682,11 +807,11 @@ impl Cartridge {
     }
 
     fn on_read(&self, addr: u16) -> u8 {
-        self.mbc.on_read(addr)
+        self.mbc.read(addr)
     }
 
     fn on_write(&mut self, addr: u16, value: u8) {
-        self.mbc.on_write(addr, value)
+        self.mbc.write(addr, value)
     }
 }
 
@@ -697,8 +822,13 @@ pub struct Mbc {
 }
 
 impl Mbc {
-    pub fn new(hw: HardwareHandle, rom: Vec<u8>, color: bool) -> Self {
-        let cartridge = Cartridge::new(hw, rom);
+    pub fn new(
+        hw: HardwareHandle,
+        rom: Vec<u8>,
+        color: bool,
+        mapper: Option<Box<dyn Mapper>>,
+    ) -> Self {
+        let cartridge = Cartridge::new(hw, rom, mapper);
 
         cartridge.show_info();
 
@@ -735,12 +865,31 @@ impl Mbc {
     }
 
     pub(crate) fn on_write(&mut self, addr: u16, value: u8) {
-        if self.use_boot_rom && addr < 0x100 {
-            unreachable!("Writing to boot ROM")
-        } else if addr == 0xff50 {
+        // Writes to the boot ROM area go through to the cartridge registers.
+        if addr == 0xff50 {
             self.use_boot_rom = false;
         } else {
             self.cartridge.on_write(addr, value)
         }
     }
+
+    pub(crate) fn step(&mut self, cycles: usize) {
+        self.cartridge.mbc.step(cycles);
+    }
+
+    pub(crate) fn rom_bank(&self) -> usize {
+        self.cartridge.mbc.rom_bank()
+    }
+
+    pub(crate) fn save(&mut self) {
+        self.cartridge.mbc.save()
+    }
+
+    pub(crate) fn save_state(&self) -> Vec<u8> {
+        self.cartridge.mbc.save_state()
+    }
+
+    pub(crate) fn load_state(&mut self, state: &[u8]) {
+        self.cartridge.mbc.load_state(state)
+    }
 }
@@ -1,6 +1,7 @@
 use crate::apu::Apu;
 use crate::cgb::Cgb;
-use crate::cpu::Sys;
+use crate::cpu::{Stop, Sys};
+use crate::debug::{Access, AccessKind, Exception};
 use crate::divider::Divider;
 use crate::dma::{Dma, DmaRequest};
 use crate::gpu::Gpu;
@@ -8,11 +9,14 @@ use crate::hardware::HardwareHandle;
 use crate::hram::Hram;
 use crate::ic::{Ic, Irq};
 use crate::joypad::Joypad;
+use crate::mapper::Mapper;
 use crate::mbc::Mbc;
 use crate::serial::Serial;
+use crate::system::Config;
 use crate::timer::Timer;
 use crate::wram::Wram;
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
+use core::cell::RefCell;
 use log::*;
 
 /// The memory management unit (MMU)
@@ -33,18 +37,38 @@ pub struct Mmu {
     apu: Apu,
     dma: Dma,
     cgb: Cgb,
+    color: bool,
+    restrict: bool,
+    strict: bool,
+    exceptions: RefCell<Vec<Exception>>,
+    /// Remaining CPU cycles of the pause caused by the speed switch.
+    speed_pause: usize,
+    /// Set in STOP mode.
+    stopped: bool,
 }
 
 impl Mmu {
     /// Create a new MMU instance.
-    pub fn new(hw: HardwareHandle, rom: Vec<u8>, color: bool) -> Mmu {
+    pub fn new(
+        hw: HardwareHandle,
+        rom: Vec<u8>,
+        cfg: &Config,
+        mapper: Option<Box<dyn Mapper>>,
+    ) -> Mmu {
         let irq = Irq::new();
+        let color = cfg.color;
 
         Mmu {
             wram: Wram::new(color),
             hram: Hram::new(),
-            gpu: Gpu::new(hw.clone(), irq.clone(), color),
-            mbc: Mbc::new(hw.clone(), rom, color),
+            gpu: Gpu::new(
+                hw.clone(),
+                irq.clone(),
+                color,
+                cfg.sprite_limit,
+                cfg.pixel_fifo,
+            ),
+            mbc: Mbc::new(hw.clone(), rom, color, mapper),
             div: Divider::new(),
             timer: Timer::new(irq.clone()),
             ic: Ic::new(irq.clone()),
@@ -53,18 +77,38 @@ impl Mmu {
             apu: Apu::new(hw),
             dma: Dma::new(),
             cgb: Cgb::new(color),
+            color,
+            restrict: cfg.access_restriction,
+            strict: cfg.strict,
+            exceptions: RefCell::new(Vec::new()),
+            speed_pause: 0,
+            stopped: false,
         }
     }
 
-    fn io_read(&self, addr: u16) -> u8 {
-        match addr {
+    /// Check if the I/O register exists only in CGB mode.
+    fn is_cgb_only(addr: u16) -> bool {
+        matches!(
+            addr,
+            0xff4d | 0xff4f | 0xff51..=0xff55 | 0xff68..=0xff6b | 0xff70
+        )
+    }
+
+    /// Reads the I/O register. Returns `None` for the registers which aren't mapped, e.g. 0xff03,
+    /// or implemented, e.g. the infrared port (0xff56), which read as 0xff (open bus).
+    /// The CGB-only registers aren't mapped in DMG mode.
+    fn io_read(&self, addr: u16) -> Option<u8> {
+        if !self.color && Self::is_cgb_only(addr) {
+            debug!("read CGB-only i/o in DMG mode addr={:04x}", addr);
+            return None;
+        }
+
+        let v = match addr {
             0xff00 => self.joypad.read(),
             0xff01 => self.serial.get_data(),
             0xff02 => self.serial.get_ctrl(),
-            0xff03 => todo!("i/o write: addr={:04x}", addr),
             0xff04 => self.div.on_read(),
             0xff05..=0xff07 => self.timer.on_read(addr),
-            0xff08..=0xff0e => todo!("i/o read: addr={:04x}", addr),
             0xff0f => self.ic.read_flags(),
             0xff10 => self.apu.read_tone_sweep(),
             0xff11 => self.apu.read_tone_wave(0),
@@ -107,10 +151,9 @@ impl Mmu {
             0xff53 => self.gpu.read_hdma_dst_high(),
             0xff54 => self.gpu.read_hdma_dst_low(),
             0xff55 => self.gpu.read_hdma_start(),
-            0xff56 => todo!("ir"),
-            0xff68 => todo!("cgb bg palette index"),
+            0xff68 => self.gpu.read_bg_color_palette_select(),
             0xff69 => self.gpu.read_bg_color_palette(),
-            0xff6a => todo!("cgb bg palette data"),
+            0xff6a => self.gpu.read_obj_color_palette_select(),
             0xff6b => self.gpu.read_obj_color_palette(),
             0xff70 => self.wram.get_bank(),
             0xff76 => self.apu.read_pcm12(),
@@ -118,20 +161,30 @@ impl Mmu {
             0x0000..=0xfeff | 0xff80..=0xffff => unreachable!("read non-i/o addr={:04x}", addr),
             _ => {
                 warn!("read unknown i/o addr={:04x}", addr);
-                0xff
+                return None;
             }
-        }
+        };
+
+        Some(v)
     }
 
-    fn io_write(&mut self, addr: u16, v: u8) {
+    /// Writes the I/O register. Returns `false` for the registers which aren't mapped or implemented,
+    /// where the write is ignored. The CGB-only registers aren't mapped in DMG mode.
+    fn io_write(&mut self, addr: u16, v: u8) -> bool {
+        if !self.color && Self::is_cgb_only(addr) {
+            debug!(
+                "write CGB-only i/o in DMG mode addr={:04x}, v={:02x}",
+                addr, v
+            );
+            return false;
+        }
+
         match addr {
             0xff00 => self.joypad.write(v),
             0xff01 => self.serial.set_data(v),
             0xff02 => self.serial.set_ctrl(v),
-            0xff03 => todo!("i/o write: addr={:04x}, v={:02x}", addr, v),
-            0xff04 => self.div.on_write(v),
+            0xff04 => self.reset_div(),
             0xff05..=0xff07 => self.timer.on_write(addr, v),
-            0xff08..=0xff0e => todo!("i/o write: addr={:04x}, v={:02x}", addr, v),
             0xff0f => self.ic.write_flags(v),
             0xff10 => self.apu.write_tone_sweep(v),
             0xff11 => self.apu.write_tone_wave(0, v),
@@ -175,7 +228,6 @@ impl Mmu {
             0xff53 => self.gpu.write_hdma_dst_high(v),
             0xff54 => self.gpu.write_hdma_dst_low(v),
             0xff55 => self.gpu.write_hdma_start(v),
-            0xff56 => todo!("ir"),
             0xff68 => self.gpu.select_bg_color_palette(v),
             0xff69 => self.gpu.write_bg_color_palette(v),
             0xff6a => self.gpu.select_obj_color_palette(v),
@@ -185,7 +237,108 @@ impl Mmu {
             0x0000..=0xfeff | 0xff80..=0xffff => {
                 unreachable!("write non-i/o addr={:04x}, v={:02x}", addr, v)
             }
-            _ => warn!("write unknown i/o addr={:04x}, v={:02x}", addr, v),
+            _ => {
+                warn!("write unknown i/o addr={:04x}, v={:02x}", addr, v);
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Record the access to the unmapped I/O register as an exception in strict mode.
+    fn raise(&self, kind: AccessKind, addr: u16, value: u8) {
+        if self.strict {
+            self.exceptions
+                .borrow_mut()
+                .push(Exception::UnmappedIo(Access {
+                    kind,
+                    addr,
+                    value,
+                    pc: 0,
+                }));
+        }
+    }
+
+    /// Take the exceptions raised by the CPU accesses since the last call.
+    pub(crate) fn take_exceptions(&mut self) -> Vec<Exception> {
+        core::mem::take(self.exceptions.get_mut())
+    }
+
+    /// Reset the system counter, which may increment the timer.
+    fn reset_div(&mut self) {
+        self.div.on_write(0);
+        self.timer.set_div(self.div.system_counter());
+    }
+
+    /// Check if the CPU runs in CGB double speed mode.
+    pub(crate) fn double_speed(&self) -> bool {
+        self.cgb.double_speed()
+    }
+
+    /// The ROM bank currently mapped to 0x4000 - 0x7fff.
+    pub(crate) fn rom_bank(&self) -> usize {
+        self.mbc.rom_bank()
+    }
+
+    /// Flush the battery-backed cartridge RAM.
+    pub(crate) fn save_ram(&mut self) {
+        self.mbc.save()
+    }
+
+    /// Serialize the cartridge mapper state.
+    pub(crate) fn save_mapper_state(&self) -> Vec<u8> {
+        self.mbc.save_state()
+    }
+
+    /// Restore the cartridge mapper state.
+    pub(crate) fn load_mapper_state(&mut self, state: &[u8]) {
+        self.mbc.load_state(state)
+    }
+
+    /// Check if the CPU access to the address is blocked by the PPU or OAM DMA.
+    fn blocked(&self, addr: u16) -> bool {
+        if !self.restrict {
+            return false;
+        }
+
+        match addr {
+            0x8000..=0x9fff => self.gpu.vram_locked(),
+            0xfe00..=0xfe9f => self.gpu.oam_locked() || self.dma.active(),
+            0xff69 | 0xff6b => self.gpu.vram_locked(),
+            _ => false,
+        }
+    }
+
+    /// Reads one byte bypassing the access restriction.
+    pub(crate) fn read(&self, addr: u16) -> u8 {
+        match addr {
+            0x0000..=0x7fff => self.mbc.on_read(addr),
+            0x8000..=0x9fff => self.gpu.read_vram(addr),
+            0xa000..=0xbfff => self.mbc.on_read(addr),
+            0xc000..=0xfdff => self.wram.get8(addr),
+            0xfe00..=0xfe9f => self.gpu.read_oam(addr),
+            0xfea0..=0xfeff => 0, // Unusable range
+            0xff00..=0xff7f => self.io_read(addr).unwrap_or(0xff),
+            0xff80..=0xfffe => self.hram.get8(addr),
+            0xffff..=0xffff => self.ic.read_enabled(),
+        }
+    }
+
+    /// Writes one byte bypassing the access restriction.
+    pub(crate) fn write(&mut self, addr: u16, v: u8) {
+        match addr {
+            0x0000..=0x7fff => self.mbc.on_write(addr, v),
+            0x8000..=0x9fff => self.gpu.write_vram(addr, v),
+            0xa000..=0xbfff => self.mbc.on_write(addr, v),
+            0xc000..=0xfdff => self.wram.set8(addr, v),
+            0xfe00..=0xfe9f => self.gpu.write_oam(addr, v),
+            0xfea0..=0xfeff => {} // Unusable range
+            0xff00..=0xff7f => {
+                self.io_write(addr, v);
+            }
+            0xff80..=0xfffe => self.hram.set8(addr, v),
+            0xffff..=0xffff => self.ic.write_enabled(v),
         }
     }
 
@@ -197,7 +350,7 @@ impl Mmu {
             req.len()
         );
         for i in 0..req.len() {
-            self.set8(req.dst() + i, self.get8(req.src() + i));
+            self.write(req.dst() + i, self.read(req.src() + i));
         }
     }
 }
@@ -215,53 +368,114 @@ impl Sys for Mmu {
 
     /// Reads one byte from the given address in the memory.
     fn get8(&self, addr: u16) -> u8 {
-        match addr {
-            0x0000..=0x7fff => self.mbc.on_read(addr),
-            0x8000..=0x9fff => self.gpu.read_vram(addr),
-            0xa000..=0xbfff => self.mbc.on_read(addr),
-            0xc000..=0xfdff => self.wram.get8(addr),
-            0xfe00..=0xfe9f => self.gpu.read_oam(addr),
-            0xfea0..=0xfeff => 0, // Unusable range
-            0xff00..=0xff7f => self.io_read(addr),
-            0xff80..=0xfffe => self.hram.get8(addr),
-            0xffff..=0xffff => self.ic.read_enabled(),
+        if self.blocked(addr) {
+            trace!("Blocked read: {:04x}", addr);
+            return 0xff;
         }
+        if self.dma.conflict(addr) {
+            // The CPU sees the byte being transferred by OAM DMA.
+            trace!("DMA bus conflict on read: {:04x}", addr);
+            return self.dma.value();
+        }
+        if (0xff00..=0xff7f).contains(&addr) {
+            return self.io_read(addr).unwrap_or_else(|| {
+                self.raise(AccessKind::Read, addr, 0xff);
+                0xff
+            });
+        }
+        self.read(addr)
     }
 
     /// Writes one byte at the given address in the memory.
     fn set8(&mut self, addr: u16, v: u8) {
-        match addr {
-            0x0000..=0x7fff => self.mbc.on_write(addr, v),
-            0x8000..=0x9fff => self.gpu.write_vram(addr, v),
-            0xa000..=0xbfff => self.mbc.on_write(addr, v),
-            0xc000..=0xfdff => self.wram.set8(addr, v),
-            0xfe00..=0xfe9f => self.gpu.write_oam(addr, v),
-            0xfea0..=0xfeff => {} // Unusable range
-            0xff00..=0xff7f => self.io_write(addr, v),
-            0xff80..=0xfffe => self.hram.set8(addr, v),
-            0xffff..=0xffff => self.ic.write_enabled(v),
+        if self.blocked(addr) || self.dma.conflict(addr) {
+            trace!("Blocked write: {:04x} {:02x}", addr, v);
+            return;
+        }
+        if (0xff00..=0xff7f).contains(&addr) {
+            if !self.io_write(addr, v) {
+                self.raise(AccessKind::Write, addr, v);
+            }
+            return;
         }
+        self.write(addr, v)
     }
 
     /// Updates the machine state by the given cycles
     fn step(&mut self, cycles: usize) {
-        if let Some(req) = self.dma.step(cycles) {
-            self.run_dma(req);
+        if self.stopped {
+            // Everything stops until one of the selected joypad lines goes low.
+            self.joypad.poll();
+            if self.joypad.pressed() {
+                info!("Wake up from STOP mode");
+                self.stopped = false;
+            }
+            return;
+        }
+
+        // In double speed mode, the devices clocked by the CPU (timer, DIV, serial and OAM DMA)
+        // run twice as fast, while PPU and APU keep running in real time.
+        let double_speed = self.cgb.double_speed();
+        let dots = if double_speed { cycles / 2 } else { cycles };
+
+        for _ in 0..cycles / 4 {
+            if let Some((src, dst)) = self.dma.tick() {
+                let v = self.read(src);
+                self.dma.transfer(v);
+                self.gpu.write_oam(dst, v);
+            }
         }
-        if let Some(req) = self.gpu.step(cycles) {
+        if let Some(req) = self.gpu.step(dots) {
             self.run_dma(req);
         }
-        let div_apu = self.div.step(cycles);
-        self.apu.step(cycles, div_apu);
-        self.timer.step(cycles);
+        let div = if self.speed_pause > 0 {
+            // DIV and timer stop while the speed is being switched.
+            self.speed_pause = self.speed_pause.saturating_sub(cycles);
+            self.div.counter()
+        } else {
+            for _ in 0..cycles / 4 {
+                self.div.step(4);
+                self.timer.step(self.div.system_counter());
+            }
+            self.div.counter()
+        };
+        // The frame sequencer is clocked by DIV bit 5 instead of bit 4 in double speed mode.
+        let div_apu = if double_speed { div >> 1 } else { div };
+        self.apu.step(dots, div_apu);
         self.serial.step(cycles);
+        self.mbc.step(cycles);
         self.joypad.poll();
     }
 
     /// Stop instruction is called.
-    fn stop(&mut self) {
-        error!("STOP instruction is called");
-        self.cgb.try_switch_speed();
+    fn stop(&mut self) -> Stop {
+        // With a pending interrupt, STOP is a 1-byte instruction.
+        let skip = self.ic.peek().is_none();
+
+        if self.joypad.pressed() {
+            // With a button held, STOP doesn't enter STOP mode nor reset DIV.
+            debug!("STOP with button held");
+            return Stop { skip, halt: skip };
+        }
+
+        self.reset_div();
+
+        if self.cgb.try_switch_speed() {
+            info!("Speed switched: double speed={}", self.cgb.double_speed());
+            // The CPU pauses for 2050 machine cycles.
+            self.speed_pause = 2050 * 4;
+        } else {
+            info!("Enter STOP mode");
+            self.stopped = true;
+            self.gpu.blank();
+        }
+
+        Stop { skip, halt: false }
+    }
+
+    /// HDMA/GDMA, the speed switch and STOP mode stall the CPU.
+    fn stalled(&self) -> bool {
+        self.gpu.hdma_active() || self.speed_pause > 0 || self.stopped
     }
 }
 
@@ -309,5 +523,38 @@ impl Sys for Ram {
 
     fn step(&mut self, _: usize) {}
 
-    fn stop(&mut self) {}
+    fn stop(&mut self) -> Stop {
+        Stop::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::TestHardware;
+    use alloc::vec;
+
+    #[test]
+    fn test_dma_conflict() {
+        // The bus conflict doesn't depend on the access restriction.
+        let hw = HardwareHandle::new(TestHardware::default());
+        let mut mmu = Mmu::new(hw, vec![0; 0x8000], &Config::new(), None);
+
+        mmu.set8(0xc000, 0x12);
+        mmu.set8(0xc001, 0x34);
+        mmu.set8(0xc100, 0x56);
+        mmu.set8(0xff80, 0x78);
+
+        // Start the transfer from WRAM and run it for 2 bytes.
+        mmu.set8(0xff46, 0xc0);
+        mmu.step(12);
+
+        // The CPU sees the byte on the bus in the same memory area, but not in HRAM.
+        assert_eq!(mmu.get8(0xc100), 0x34);
+        assert_eq!(mmu.get8(0xff80), 0x78);
+
+        // Writes on the bus are ignored.
+        mmu.set8(0xc100, 0x9a);
+        assert_eq!(mmu.read(0xc100), 0x56);
+    }
 }
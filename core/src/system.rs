@@ -1,8 +1,10 @@
-use crate::cpu::{Cpu, CPU_FREQ_HZ};
-use crate::debug::Debugger;
+use crate::cpu::{Cpu, Lockup, CPU_FREQ_HZ};
+use crate::debug::{self, Debugger};
 use crate::fc::FreqControl;
 use crate::hardware::{Hardware, HardwareHandle};
+use crate::mapper::{Mapper, MapperRegistry};
 use crate::mmu::Mmu;
+use alloc::{boxed::Box, vec::Vec};
 use log::*;
 
 /// Configuration of the emulator.
@@ -15,6 +17,18 @@ pub struct Config {
     pub(crate) native_speed: bool,
     /// Emulate Gameboy Color
     pub(crate) color: bool,
+    /// Limit the number of sprites per line to 10 as the hardware does.
+    pub(crate) sprite_limit: bool,
+    /// Use the dot-accurate pixel FIFO renderer.
+    pub(crate) pixel_fifo: bool,
+    /// Block the CPU access to VRAM/OAM while the PPU or OAM DMA is using them.
+    pub(crate) access_restriction: bool,
+    /// Report the accesses to the unmapped I/O registers to the debugger.
+    pub(crate) strict: bool,
+    /// Custom mapper which overrides the one selected by the cartridge type.
+    pub(crate) mapper: Option<Box<dyn Mapper>>,
+    /// Custom mappers keyed by the cartridge type.
+    pub(crate) mappers: MapperRegistry,
 }
 
 impl Default for Config {
@@ -33,6 +47,12 @@ impl Config {
             rate_limit_interval: 20_000,
             native_speed: false,
             color: false,
+            sprite_limit: true,
+            pixel_fifo: false,
+            access_restriction: false,
+            strict: false,
+            mapper: None,
+            mappers: MapperRegistry::new(),
         }
     }
 
@@ -59,6 +79,64 @@ impl Config {
         self.color = color;
         self
     }
+
+    /// Set the flag to limit the number of sprites per line to 10 as the hardware does.
+    /// Disabling the limit reduces sprite flicker in some games.
+    pub fn sprite_limit(mut self, limit: bool) -> Self {
+        self.sprite_limit = limit;
+        self
+    }
+
+    /// Set the flag to use the dot-accurate pixel FIFO renderer instead of the scanline renderer.
+    /// The pixel FIFO renderer emulates mid-scanline register writes and variable mode 3 length,
+    /// but it's slower than the scanline renderer.
+    pub fn pixel_fifo(mut self, pixel_fifo: bool) -> Self {
+        self.pixel_fifo = pixel_fifo;
+        self
+    }
+
+    /// Set the flag to block the CPU access to VRAM, OAM and CGB palette RAM
+    /// while the PPU or OAM DMA is using them as the hardware does.
+    /// Reads return 0xff and writes are ignored during the blocked periods.
+    pub fn access_restriction(mut self, restrict: bool) -> Self {
+        self.access_restriction = restrict;
+        self
+    }
+
+    /// Set the flag to report the CPU accesses to the I/O registers which aren't mapped or implemented
+    /// to the debugger as [`Exception`](crate::debug::Exception)s, including the CGB-only registers in DMG mode.
+    /// The accesses never abort the emulator: reads return 0xff (open bus) and writes are ignored either way.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Use the custom mapper regardless of the cartridge type.
+    pub fn mapper(mut self, mapper: Box<dyn Mapper>) -> Self {
+        self.mapper = Some(mapper);
+        self
+    }
+
+    /// Register the custom mapper for the cartridge type, which is the byte at 0x147 in the ROM.
+    pub fn register_mapper<F>(mut self, code: u8, factory: F) -> Self
+    where
+        F: Fn(&[u8]) -> Box<dyn Mapper> + 'static,
+    {
+        self.mappers.register(code, factory);
+        self
+    }
+
+    /// Set the registry of custom mappers.
+    pub fn mapper_registry(mut self, mappers: MapperRegistry) -> Self {
+        self.mappers = mappers;
+        self
+    }
+
+    fn take_mapper(&mut self, rom: &[u8]) -> Option<Box<dyn Mapper>> {
+        self.mapper
+            .take()
+            .or_else(|| self.mappers.create(*rom.get(0x147)?, rom))
+    }
 }
 
 /// Represents the entire emulator context.
@@ -67,7 +145,8 @@ pub struct System<D> {
     hw: HardwareHandle,
     fc: FreqControl,
     cpu: Cpu,
-    _dbg: D,
+    dbg: D,
+    cycles: u64,
 }
 
 impl<D> System<D>
@@ -75,7 +154,7 @@ where
     D: Debugger + 'static,
 {
     /// Create a new emulator context.
-    pub fn new<T>(cfg: Config, rom: &[u8], hw: T, dbg: D) -> Self
+    pub fn new<T>(mut cfg: Config, rom: &[u8], hw: T, mut dbg: D) -> Self
     where
         T: Hardware + 'static,
     {
@@ -85,8 +164,11 @@ where
 
         let mut fc = FreqControl::new(hw.clone(), &cfg);
 
-        let mmu = Mmu::new(hw.clone(), rom.to_vec(), cfg.color);
-        let cpu = Cpu::new(mmu);
+        let mapper = cfg.take_mapper(rom);
+        let mmu = Mmu::new(hw.clone(), rom.to_vec(), &cfg, mapper);
+        let mut cpu = Cpu::new(mmu);
+
+        dbg.init(&mut cpu);
 
         info!("Starting...");
 
@@ -97,7 +179,8 @@ where
             hw,
             fc,
             cpu,
-            _dbg: dbg,
+            dbg,
+            cycles: 0,
         }
     }
 
@@ -109,7 +192,16 @@ where
             return false;
         }
 
-        let time = self.cpu.execute();
+        let time = debug::execute(&mut self.dbg, &mut self.cpu);
+
+        // The CPU runs twice as many cycles in the same time in double speed mode.
+        let time = if self.cpu.sys().double_speed() {
+            time / 2
+        } else {
+            time
+        };
+
+        self.cycles += time as u64;
 
         if !self.cfg.native_speed {
             self.fc.adjust(time);
@@ -117,6 +209,35 @@ where
 
         true
     }
+
+    /// Get the lockup if the CPU has executed an illegal opcode.
+    /// The emulation can continue, but the CPU never executes instructions again.
+    pub fn lockup(&self) -> Option<Lockup> {
+        self.cpu.lockup()
+    }
+
+    /// Get the number of clock cycles elapsed since the emulation started.
+    /// The cycles are counted at the normal speed clock also in CGB double speed mode.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Serialize the state of the cartridge mapper.
+    pub fn save_mapper_state(&self) -> Vec<u8> {
+        self.cpu.sys().save_mapper_state()
+    }
+
+    /// Restore the state of the cartridge mapper serialized by [`System::save_mapper_state`].
+    pub fn load_mapper_state(&mut self, state: &[u8]) {
+        self.cpu.sys_mut().load_mapper_state(state)
+    }
+}
+
+impl<D> Drop for System<D> {
+    fn drop(&mut self) {
+        // Flush the battery-backed RAM on exit.
+        self.cpu.sys_mut().save_ram();
+    }
 }
 
 /// Run the emulator with the given configuration.
@@ -138,3 +259,64 @@ fn run_inner<T: Hardware + 'static, D: Debugger + 'static>(cfg: Config, rom: &[u
     let mut sys = System::new(cfg, rom, hw, dbg);
     while sys.poll() {}
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debug::NullDebugger;
+    use crate::hardware::TestHardware;
+    use alloc::{rc::Rc, vec};
+    use core::cell::Cell;
+
+    struct Battery {
+        saved: Rc<Cell<usize>>,
+    }
+
+    impl Mapper for Battery {
+        fn read(&self, _: u16) -> u8 {
+            0
+        }
+
+        fn write(&mut self, _: u16, _: u8) {}
+
+        fn save(&mut self) {
+            self.saved.set(self.saved.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_save_on_exit() {
+        let saved = Rc::new(Cell::new(0));
+        let mapper = Battery {
+            saved: saved.clone(),
+        };
+        let cfg = Config::new().mapper(Box::new(mapper));
+
+        let sys = System::new(cfg, &[0; 0x8000], TestHardware::default(), NullDebugger);
+        assert_eq!(saved.get(), 0);
+
+        drop(sys);
+        assert_eq!(saved.get(), 1);
+    }
+
+    #[test]
+    fn test_save_on_ram_disable() {
+        // MBC1+RAM+BATTERY, MBC3+RAM+BATTERY
+        for cartridge in [0x03, 0x13] {
+            let hw = TestHardware::default();
+
+            let mut rom = vec![0; 0x8000];
+            rom[0x147] = cartridge;
+
+            let mut mmu = Mmu::new(HardwareHandle::new(hw.clone()), rom, &Config::new(), None);
+
+            mmu.write(0x0000, 0x0a);
+            mmu.write(0xa000, 0x42);
+            assert!(hw.saved.borrow().is_empty());
+
+            mmu.write(0x0000, 0x00);
+            assert_eq!(hw.saved.borrow().len(), 1);
+            assert_eq!(hw.saved.borrow()[0][0], 0x42);
+        }
+    }
+}
@@ -1,11 +1,17 @@
-use crate::{clock::PrescaledTimer, ic::Irq};
+use crate::ic::Irq;
 use bitfield_struct::bitfield;
 use log::*;
 
 pub struct Timer {
     ctrl: Ctrl,
-    timer: PrescaledTimer,
+    counter: u8,
     load: u8,
+    /// The system counter seen last time.
+    div: u16,
+    /// TIMA overflowed in the last machine cycle, and is going to be reloaded.
+    overflow: bool,
+    /// TIMA is reloaded from TMA in the current machine cycle.
+    reloading: bool,
     irq: Irq,
 }
 
@@ -21,41 +27,65 @@ struct Ctrl {
 impl Timer {
     pub fn new(irq: Irq) -> Self {
         Self {
-            timer: PrescaledTimer::builder()
-                .disable()
-                .frequency(4096)
-                .interval(256)
-                .build(),
             ctrl: Ctrl::default(),
+            counter: 0,
             load: 0,
+            div: 0,
+            overflow: false,
+            reloading: false,
             irq,
         }
     }
 
-    pub fn step(&mut self, cycles: usize) {
-        if self.timer.step(cycles) {
-            self.timer.set_counter(self.load as usize);
+    /// Proceed the timer by one machine cycle with the updated system counter.
+    pub fn step(&mut self, div: u16) {
+        self.reloading = false;
+
+        if self.overflow {
+            // TIMA is reloaded and the interrupt is requested one machine cycle after the overflow.
+            self.overflow = false;
+            self.reloading = true;
+            self.counter = self.load;
             self.irq.timer(true);
         }
+
+        self.set_div(div);
     }
 
-    fn reset(&mut self, select: usize) {
-        let freq = match select {
-            0 => 4096,
-            1 => 262144,
-            2 => 65536,
-            3 => 16384,
+    /// Update the system counter, e.g. by writing DIV register.
+    pub fn set_div(&mut self, div: u16) {
+        let old = self.signal();
+        self.div = div;
+        self.update(old);
+    }
+
+    /// The bit of the system counter selected by TAC, ANDed with the enable bit.
+    fn signal(&self) -> bool {
+        let bit = match self.ctrl.select() {
+            0 => 9, // 4096 Hz
+            1 => 3, // 262144 Hz
+            2 => 5, // 65536 Hz
+            3 => 7, // 16384 Hz
             _ => unreachable!(),
         };
-        self.timer.set_frequency(freq);
+        self.ctrl.enable() && self.div & (1 << bit) != 0
+    }
+
+    /// TIMA is incremented on the falling edge of the signal.
+    fn update(&mut self, old: bool) {
+        if old && !self.signal() {
+            let (counter, overflow) = self.counter.overflowing_add(1);
+            self.counter = counter;
+            self.overflow = overflow;
+        }
     }
 
     pub(crate) fn on_read(&self, addr: u16) -> u8 {
         info!("Timer read: {:04x}", addr);
         match addr {
-            0xff05 => self.timer.counter() as u8,
+            0xff05 => self.counter,
             0xff06 => self.load,
-            0xff07 => self.ctrl.into_bits(),
+            0xff07 => self.ctrl.into_bits() | 0xf8,
             _ => unreachable!("invalid timer read addr={:04x}", addr),
         }
     }
@@ -63,25 +93,118 @@ impl Timer {
     pub(crate) fn on_write(&mut self, addr: u16, value: u8) {
         info!("Timer write: {:04x} {:02x}", addr, value);
         match addr {
-            0xff05 => self.timer.set_counter(value as usize),
-            0xff06 => self.load = value,
-            0xff07 => {
-                let prev_enable = self.ctrl.enable();
-
-                self.ctrl = Ctrl::from_bits(value);
-
-                if self.ctrl.enable() {
-                    self.timer.enable();
-
-                    if !prev_enable {
-                        debug!("Timer started");
-                        self.reset(self.ctrl.select());
-                    }
-                } else {
-                    self.timer.disable();
+            0xff05 => {
+                if self.reloading {
+                    // The write is ignored in the machine cycle when TIMA is reloaded.
+                    return;
                 }
+                // The write cancels the pending reload and interrupt.
+                self.overflow = false;
+                self.counter = value;
+            }
+            0xff06 => {
+                self.load = value;
+                if self.reloading {
+                    // The new value is also loaded to TIMA.
+                    self.counter = value;
+                }
+            }
+            0xff07 => {
+                let old = self.signal();
+                self.ctrl = Ctrl::from_bits(value & 0x07);
+                // Changing the frequency or disabling the timer may cause a falling edge.
+                self.update(old);
             }
             _ => unreachable!("invalid timer write addr={:04x}, value={:04x}", addr, value),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ic::Ic;
+
+    /// Run the timer by the machine cycles, and return the system counter.
+    fn run(timer: &mut Timer, div: u16, mcycles: u16) -> u16 {
+        let mut div = div;
+        for _ in 0..mcycles {
+            div = div.wrapping_add(4);
+            timer.step(div);
+        }
+        div
+    }
+
+    #[test]
+    fn test_timer_frequency() {
+        let mut timer = Timer::new(Irq::new());
+
+        timer.on_write(0xff07, 0x05);
+        let div = run(&mut timer, 0, 4 * 10);
+        assert_eq!(timer.on_read(0xff05), 10);
+
+        timer.on_write(0xff07, 0x04);
+        run(&mut timer, div, 256 * 3);
+        assert_eq!(timer.on_read(0xff05), 13);
+        assert_eq!(timer.on_read(0xff07), 0xfc);
+    }
+
+    #[test]
+    fn test_timer_glitch() {
+        let mut timer = Timer::new(Irq::new());
+        timer.on_write(0xff07, 0x05);
+
+        // Resetting DIV while bit 3 is set increments TIMA.
+        let div = run(&mut timer, 0, 2);
+        assert_eq!(div & 0x08, 0x08);
+        timer.set_div(0);
+        assert_eq!(timer.on_read(0xff05), 1);
+
+        // Disabling the timer while bit 3 is set increments TIMA.
+        run(&mut timer, 0, 2);
+        timer.on_write(0xff07, 0x01);
+        assert_eq!(timer.on_read(0xff05), 2);
+
+        // Changing the frequency to the one whose bit is low increments TIMA.
+        timer.on_write(0xff07, 0x05);
+        timer.on_write(0xff07, 0x06);
+        assert_eq!(timer.on_read(0xff05), 3);
+    }
+
+    #[test]
+    fn test_timer_reload() {
+        let irq = Irq::new();
+        let mut ic = Ic::new(irq.clone());
+        let mut timer = Timer::new(irq);
+
+        timer.on_write(0xff06, 0x80);
+        timer.on_write(0xff05, 0xff);
+        timer.on_write(0xff07, 0x05);
+
+        // TIMA reads 0 for one machine cycle after overflow.
+        let div = run(&mut timer, 0, 4);
+        assert_eq!(timer.on_read(0xff05), 0x00);
+        assert_eq!(ic.read_flags() & 0x04, 0);
+
+        let div = run(&mut timer, div, 1);
+        assert_eq!(timer.on_read(0xff05), 0x80);
+        assert_eq!(ic.read_flags() & 0x04, 0x04);
+
+        // Writing TIMA in the reload cycle is ignored, and writing TMA is reflected to TIMA.
+        timer.on_write(0xff05, 0x10);
+        assert_eq!(timer.on_read(0xff05), 0x80);
+        timer.on_write(0xff06, 0x90);
+        assert_eq!(timer.on_read(0xff05), 0x90);
+
+        // Writing TIMA right after overflow cancels the reload and the interrupt.
+        let div = run(&mut timer, div, 1);
+        timer.on_write(0xff05, 0xff);
+        ic.write_flags(0);
+        let div = run(&mut timer, div, 2);
+        assert_eq!(timer.on_read(0xff05), 0x00);
+        timer.on_write(0xff05, 0x20);
+        run(&mut timer, div, 1);
+        assert_eq!(timer.on_read(0xff05), 0x20);
+        assert_eq!(ic.read_flags() & 0x04, 0);
+    }
+}
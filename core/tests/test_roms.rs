@@ -1,11 +1,15 @@
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     io::Write,
     rc::Rc,
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use rgy::{VRAM_HEIGHT, VRAM_WIDTH};
+use rgy::{
+    cpu::Cpu,
+    debug::{RegisterProbe, Signature},
+    VRAM_HEIGHT, VRAM_WIDTH,
+};
 
 const SHORT_RUN: &'static str = "SHORT_RUN";
 const UPDATE_EXPECTED_DISPLAY: &'static str = "UPDATE_EXPECTED_DISPLAY";
@@ -87,6 +91,7 @@ impl Display {
 enum Expected {
     Serial(&'static str),
     Display(&'static str, Display),
+    Fibonacci,
 }
 
 impl Expected {
@@ -100,15 +105,17 @@ struct TestHardware {
     index: usize,
     is_done: bool,
     display: Display,
+    passed: Rc<Cell<bool>>,
 }
 
 impl TestHardware {
-    fn new(expected: Expected, display: Display) -> Self {
+    fn new(expected: Expected, display: Display, passed: Rc<Cell<bool>>) -> Self {
         Self {
             expected,
             index: 0,
             is_done: false,
             display,
+            passed,
         }
     }
 }
@@ -170,7 +177,7 @@ impl rgy::Hardware for TestHardware {
     fn save_ram(&mut self, _: &[u8]) {}
 
     fn sched(&mut self) -> bool {
-        !self.is_done
+        !self.is_done && !self.passed.get()
     }
 }
 
@@ -178,17 +185,29 @@ fn test_rom(expected: Expected, path: &str) {
     let display = Display::new();
 
     let rom = std::fs::read(path).unwrap();
-    let hw = TestHardware::new(expected.clone(), display.clone());
-    let mut sys = rgy::System::new(
-        rgy::Config::new().native_speed(true),
-        &rom,
-        hw,
-        rgy::debug::NullDebugger,
-    );
+    let passed = Rc::new(Cell::new(false));
+    let hw = TestHardware::new(expected.clone(), display.clone(), passed.clone());
+    let fibonacci = matches!(expected, Expected::Fibonacci);
+    let probe = RegisterProbe::new(move |sig, cpu: &Cpu| {
+        if !fibonacci {
+            return;
+        }
+        match sig {
+            Signature::Pass => passed.set(true),
+            Signature::Fail => panic!("test failed at pc {:04x}:\n{}", cpu.get_pc(), cpu),
+        }
+    });
+    let mut sys = rgy::System::new(rgy::Config::new().native_speed(true), &rom, hw, probe);
     let timeout = Duration::from_secs(if short_run() { 10 } else { 60 });
     let now = Instant::now();
 
     while sys.poll() {
+        if let Some(lockup) = sys.lockup() {
+            panic!(
+                "CPU locked up by illegal opcode {:02x} at {:04x}",
+                lockup.opcode, lockup.pc
+            );
+        }
         if now.elapsed() >= timeout {
             if let Expected::Display(filename, _) = expected {
                 if update_expected_display() {
@@ -204,6 +223,8 @@ fn test_rom(expected: Expected, path: &str) {
                         display.to_text()
                     );
                 }
+            } else if let Expected::Fibonacci = expected {
+                panic!("didn't reach the pass signature in registers");
             } else {
                 panic!("didn't match serial output");
             }
@@ -479,3 +500,581 @@ fn same_suite_channel_1_restart_nrx2_glitch() {
         "../roms/same_suite/apu/channel_1/channel_1_restart_nrx2_glitch.gb",
     );
 }
+
+// Mooneye test suite (https://github.com/Gekkio/mooneye-test-suite).
+
+#[test]
+fn mooneye_timer_div_write() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/div_write.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_rapid_toggle() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/rapid_toggle.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim00() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim00.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim00_div_trigger() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim00_div_trigger.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim01() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim01.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim01_div_trigger() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim01_div_trigger.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim10() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim10.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim10_div_trigger() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim10_div_trigger.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim11() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim11.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tim11_div_trigger() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tim11_div_trigger.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tima_reload() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tima_reload.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tima_write_reloading() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tima_write_reloading.gb",
+    );
+}
+
+#[test]
+fn mooneye_timer_tma_write_reloading() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/timer/tma_write_reloading.gb",
+    );
+}
+
+#[test]
+fn mooneye_oam_dma_basic() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/oam_dma/basic.gb",
+    );
+}
+
+#[test]
+fn mooneye_oam_dma_reg_read() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/oam_dma/reg_read.gb",
+    );
+}
+
+#[test]
+fn mooneye_oam_dma_sources_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/oam_dma/sources-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_oam_dma_restart() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/oam_dma_restart.gb",
+    );
+}
+
+#[test]
+fn mooneye_oam_dma_start() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/oam_dma_start.gb",
+    );
+}
+
+#[test]
+fn mooneye_oam_dma_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/oam_dma_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_ei_sequence() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ei_sequence.gb",
+    );
+}
+
+#[test]
+fn mooneye_ei_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ei_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_di_timing_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/di_timing-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_halt_ime0_ei() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/halt_ime0_ei.gb",
+    );
+}
+
+#[test]
+fn mooneye_halt_ime0_nointr_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/halt_ime0_nointr_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_halt_ime1_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/halt_ime1_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_halt_ime1_timing2_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/halt_ime1_timing2-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_if_ie_registers() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/if_ie_registers.gb",
+    );
+}
+
+#[test]
+fn mooneye_intr_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/intr_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_rapid_di_ei() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/rapid_di_ei.gb",
+    );
+}
+
+#[test]
+fn mooneye_reti_intr_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/reti_intr_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_reti_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/reti_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_hblank_ly_scx_timing_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/hblank_ly_scx_timing-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_intr_1_2_timing_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/intr_1_2_timing-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_intr_2_0_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/intr_2_0_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_intr_2_mode0_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/intr_2_mode0_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_intr_2_mode0_timing_sprites() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/intr_2_mode0_timing_sprites.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_intr_2_mode3_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/intr_2_mode3_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_intr_2_oam_ok_timing() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/intr_2_oam_ok_timing.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_lcdon_timing_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/lcdon_timing-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_lcdon_write_timing_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/lcdon_write_timing-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_stat_irq_blocking() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/stat_irq_blocking.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_stat_lyc_onoff() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/stat_lyc_onoff.gb",
+    );
+}
+
+#[test]
+fn mooneye_ppu_vblank_stat_intr_gs() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/ppu/vblank_stat_intr-GS.gb",
+    );
+}
+
+#[test]
+fn mooneye_interrupts_ie_push() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/acceptance/interrupts/ie_push.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_bits_bank1() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/bits_bank1.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_bits_bank2() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/bits_bank2.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_bits_mode() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/bits_mode.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_bits_ramg() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/bits_ramg.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_multicart_rom_8mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/multicart_rom_8Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_ram_256kb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/ram_256kb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_ram_64kb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/ram_64kb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_rom_16mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/rom_16Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_rom_1mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/rom_1Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_rom_2mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/rom_2Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_rom_4mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/rom_4Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_rom_512kb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/rom_512kb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc1_rom_8mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc1/rom_8Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_bits_ramg() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/bits_ramg.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_bits_romb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/bits_romb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_bits_unused() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/bits_unused.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_ram() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/ram.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_rom_1mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/rom_1Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_rom_2mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/rom_2Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc2_rom_512kb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc2/rom_512kb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_16mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_16Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_1mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_1Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_2mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_2Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_32mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_32Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_4mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_4Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_512kb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_512kb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_64mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_64Mb.gb",
+    );
+}
+
+#[test]
+fn mooneye_mbc5_rom_8mb() {
+    test_rom(
+        Expected::Fibonacci,
+        "../roms/mooneye/emulator-only/mbc5/rom_8Mb.gb",
+    );
+}